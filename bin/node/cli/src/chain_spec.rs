@@ -299,6 +299,7 @@ pub fn testnet_genesis(
 		provider: ProviderConfig {
 			resource: Default::default(),
 			resource_index: Default::default(),
+			specification_thresholds: Default::default(),
 		},
 		system: SystemConfig { code: wasm_binary_unwrap().to_vec() },
 		balances: BalancesConfig {
@@ -614,6 +615,7 @@ pub fn hamster_genesis(
 		provider: ProviderConfig {
 			resource: Default::default(),
 			resource_index: Default::default(),
+			specification_thresholds: Default::default(),
 		},
 		system: SystemConfig { code: wasm_binary_unwrap().to_vec() },
 		balances: BalancesConfig {
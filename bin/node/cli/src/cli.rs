@@ -65,6 +65,9 @@ pub enum Subcommand {
 	#[clap(subcommand)]
 	Key(sc_cli::KeySubcommand),
 
+	/// Register the local machine as a computing resource of a running node.
+	Provider(ProviderCmd),
+
 	/// Verify a signature for a message, provided on STDIN, with a given (public or secret) key.
 	Verify(sc_cli::VerifyCmd),
 
@@ -98,3 +101,65 @@ pub enum Subcommand {
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
 }
+
+/// Register the local machine as a computing resource through the RPC of a node.
+///
+/// The configuration of the resource is gathered from the system, its specification is derived
+/// on chain from the hardware benchmark scores measured here.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ProviderCmd {
+	/// Websocket RPC endpoint of the node the registration is submitted to.
+	#[clap(long, default_value = "ws://127.0.0.1:9944")]
+	pub url: String,
+
+	/// Secret URI of the provider account signing the registration, e.g. `//Alice`.
+	#[clap(long, required_unless_present = "dry-run")]
+	pub suri: Option<String>,
+
+	/// Peer id the resource is reached with.
+	#[clap(long)]
+	pub peer_id: String,
+
+	/// Public ip of the resource.
+	#[clap(long)]
+	pub public_ip: String,
+
+	/// Rent price of the resource per block.
+	#[clap(long)]
+	pub price: u128,
+
+	/// Number of blocks the resource can be rented for.
+	#[clap(long)]
+	pub rent_duration: u32,
+
+	/// Capacity of the disk offered with the resource, in GiB.
+	#[clap(long)]
+	pub storage: Option<u64>,
+
+	/// Kind of the disk offered with the resource.
+	#[clap(long, arg_enum, default_value = "ssd")]
+	pub disk_kind: ProviderDiskKind,
+
+	/// Network bandwidth of the resource, in Mbps.
+	#[clap(long)]
+	pub bandwidth: Option<u64>,
+
+	/// Directory the disk is benchmarked in, the disk is not benchmarked without it.
+	#[clap(long)]
+	pub scratch_directory: Option<std::path::PathBuf>,
+
+	/// Print the resource that would be registered instead of registering it.
+	#[clap(long)]
+	pub dry_run: bool,
+}
+
+/// Kind of the disk offered with a resource.
+#[derive(Debug, Clone, Copy, clap::ArgEnum)]
+pub enum ProviderDiskKind {
+	/// Hard disk drive.
+	Hdd,
+	/// Solid state drive.
+	Ssd,
+	/// Solid state drive attached through NVMe.
+	Nvme,
+}
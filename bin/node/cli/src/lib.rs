@@ -40,6 +40,8 @@ mod cli;
 mod command;
 #[cfg(feature = "cli")]
 mod command_helper;
+#[cfg(feature = "cli")]
+mod provider_cmd;
 
 #[cfg(feature = "cli")]
 pub use cli::*;
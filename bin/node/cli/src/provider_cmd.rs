@@ -0,0 +1,229 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `provider` subcommand, registering the local machine as a computing resource.
+
+use crate::{service::hardware_scores, ProviderCmd, ProviderDiskKind};
+use codec::Encode;
+use frame_support::BoundedVec;
+use jsonrpsee::{
+	core::client::ClientT,
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use node_primitives::{Hash, Index};
+use node_runtime::ProviderCall;
+use sc_cli::Result;
+use sp_core::{crypto::Ss58Codec, sr25519, Bytes, Pair};
+use sp_hamster::p_provider::{
+	DiskKind, HardwareScores, ResourceConfig, ResourceDimension, ResourceStringLimit,
+};
+use sp_runtime::generic::Era;
+
+/// Bytes in a unit of memory and storage.
+const GIB: u64 = 1024 * 1024 * 1024;
+
+impl ProviderCmd {
+	/// Run the command.
+	pub fn run(&self) -> Result<()> {
+		let peer_id = bounded(&self.peer_id, "peer id")?;
+		let public_ip = bounded(&self.public_ip, "public ip")?;
+		let sysinfo = sc_sysinfo::gather_sysinfo();
+		let hwbench = sc_sysinfo::gather_hwbench(self.scratch_directory.as_deref());
+
+		let config = self.resource_config(&sysinfo)?;
+		let scores = hardware_scores(&hwbench);
+
+		if self.dry_run {
+			self.print_resource(&config, &scores);
+			return Ok(())
+		}
+
+		let call = ProviderCall::register_resource {
+			peer_id,
+			public_ip,
+			config,
+			scores,
+			price: self.price,
+			rent_duration: self.rent_duration,
+		};
+
+		let suri = self.suri.as_ref().ok_or("a secret URI is required to register")?;
+		let signer = sr25519::Pair::from_string(suri, None)
+			.map_err(|e| format!("invalid secret URI: {:?}", e))?;
+
+		let runtime = tokio::runtime::Runtime::new()?;
+		let hash = runtime.block_on(submit(&self.url, signer, call.into()))?;
+		println!("Registration submitted in extrinsic {:?}", hash);
+		Ok(())
+	}
+
+	/// Configuration of the local machine, with the storage and bandwidth given on the command
+	/// line.
+	fn resource_config(
+		&self,
+		sysinfo: &sc_telemetry::SysInfo,
+	) -> Result<ResourceConfig<ResourceStringLimit>> {
+		let cpu = sysinfo.core_count.ok_or("the number of cpu cores can not be read")?;
+		let memory = sysinfo.memory.ok_or("the size of the memory can not be read")? / GIB;
+		let cpu_model = sysinfo.cpu.as_deref().unwrap_or("unknown");
+
+		let mut dimensions = Vec::new();
+		if let Some(capacity) = self.storage {
+			dimensions.push(ResourceDimension::Storage { kind: self.disk_kind.into(), capacity });
+		}
+		if let Some(bandwidth) = self.bandwidth {
+			dimensions.push(ResourceDimension::Bandwidth(bandwidth));
+		}
+
+		Ok(ResourceConfig::new(
+			cpu.into(),
+			memory,
+			bounded(sc_sysinfo::TARGET_OS, "operating system")?,
+			bounded(cpu_model, "cpu model")?,
+			dimensions.try_into().expect("at most two dimensions are declared; qed"),
+		))
+	}
+
+	/// Print the resource that `run` registers.
+	fn print_resource(
+		&self,
+		config: &ResourceConfig<ResourceStringLimit>,
+		scores: &HardwareScores,
+	) {
+		println!("Peer id: {}", self.peer_id);
+		println!("Public ip: {}", self.public_ip);
+		println!("Operating system: {}", String::from_utf8_lossy(&config.system));
+		println!("CPU: {} cores, {}", config.cpu, String::from_utf8_lossy(&config.cpu_model));
+		println!("Memory: {}GiB", config.memory);
+		for dimension in config.dimensions.iter() {
+			match dimension {
+				ResourceDimension::Storage { kind, capacity } =>
+					println!("Storage: {}GiB {}", capacity, disk_kind_name(*kind)),
+				ResourceDimension::Bandwidth(bandwidth) => println!("Bandwidth: {}Mbps", bandwidth),
+				ResourceDimension::Accelerator { model, count, memory } => println!(
+					"Accelerator: {} x {} with {}GiB",
+					count,
+					String::from_utf8_lossy(model),
+					memory
+				),
+			}
+		}
+		println!("CPU score: {}MB/s", scores.cpu_hashrate_score);
+		println!("Memory score: {}MB/s", scores.memory_memcpy_score);
+		if let Some(score) = scores.disk_sequential_write_score {
+			println!("Disk score (seq. writes): {}MB/s", score);
+		}
+		if let Some(score) = scores.disk_random_write_score {
+			println!("Disk score (rand. writes): {}MB/s", score);
+		}
+		println!("Price: {} per block for {} blocks", self.price, self.rent_duration);
+	}
+}
+
+/// The version of the runtime the signed extensions check.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeVersion {
+	spec_version: u32,
+	transaction_version: u32,
+}
+
+/// Sign `call` with the next nonce of `signer` and submit it to the node at `url`.
+async fn submit(url: &str, signer: sr25519::Pair, call: node_runtime::Call) -> Result<Hash> {
+	let client = WsClientBuilder::default()
+		.build(url)
+		.await
+		.map_err(|e| format!("connecting to {} failed: {:?}", url, e))?;
+
+	let account = sp_runtime::AccountId32::from(signer.public());
+	let genesis_hash: Hash = request(&client, "chain_getBlockHash", rpc_params!(0u32)).await?;
+	let version: RuntimeVersion = request(&client, "state_getRuntimeVersion", None).await?;
+	let nonce: Index =
+		request(&client, "system_accountNextIndex", rpc_params!(account.to_ss58check())).await?;
+
+	// the nonce already protects the registration from replays
+	let extra: node_runtime::SignedExtra = (
+		frame_system::CheckNonZeroSender::new(),
+		frame_system::CheckSpecVersion::new(),
+		frame_system::CheckTxVersion::new(),
+		frame_system::CheckGenesis::new(),
+		frame_system::CheckEra::from(Era::Immortal),
+		frame_system::CheckNonce::from(nonce),
+		frame_system::CheckWeight::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::from(0),
+	);
+	let raw_payload = node_runtime::SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		(
+			(),
+			version.spec_version,
+			version.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
+	let extrinsic = node_runtime::UncheckedExtrinsic::new_signed(
+		call,
+		account.into(),
+		node_runtime::Signature::Sr25519(signature),
+		extra,
+	);
+
+	request(&client, "author_submitExtrinsic", rpc_params!(Bytes(extrinsic.encode()))).await
+}
+
+async fn request<R: serde::de::DeserializeOwned>(
+	client: &WsClient,
+	method: &str,
+	params: Option<jsonrpsee::types::ParamsSer<'_>>,
+) -> Result<R> {
+	client
+		.request(method, params)
+		.await
+		.map_err(|e| format!("{} request failed: {:?}", method, e).into())
+}
+
+fn bounded(value: &str, what: &str) -> Result<BoundedVec<u8, ResourceStringLimit>> {
+	value.as_bytes().to_vec().try_into().map_err(|_| {
+		format!("the {} is longer than {} bytes", what, ResourceStringLimit::get()).into()
+	})
+}
+
+impl From<ProviderDiskKind> for DiskKind {
+	fn from(kind: ProviderDiskKind) -> Self {
+		match kind {
+			ProviderDiskKind::Hdd => DiskKind::Hdd,
+			ProviderDiskKind::Ssd => DiskKind::Ssd,
+			ProviderDiskKind::Nvme => DiskKind::Nvme,
+		}
+	}
+}
+
+fn disk_kind_name(kind: DiskKind) -> &'static str {
+	match kind {
+		DiskKind::Hdd => "hdd",
+		DiskKind::Ssd => "ssd",
+		DiskKind::Nvme => "nvme",
+	}
+}
@@ -26,16 +26,23 @@ use futures::prelude::*;
 use node_executor::ExecutorDispatch;
 use node_primitives::Block;
 use node_runtime::RuntimeApi;
-use sc_client_api::{BlockBackend, ExecutorProvider};
+use sc_client_api::{Backend, BlockBackend, ExecutorProvider};
 use sc_consensus_babe::{self, SlotProportion};
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::{Event, NetworkService};
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_api::ProvideRuntimeApi;
-use sp_core::crypto::Pair;
+use sp_core::{
+	crypto::Pair,
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+};
+use sp_hamster::p_provider::{HardwareScores, HWBENCH_STORAGE_KEY, PEER_ID_STORAGE_KEY};
 use sp_runtime::{generic, traits::Block as BlockT, SaturatedConversion};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+/// How often the hardware benchmarks attested by the provider offchain workers run again.
+const PROVIDER_HWBENCH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
 
 /// The full client type definition.
 pub type FullClient =
@@ -306,6 +313,36 @@ pub struct NewFullBase {
 	pub rpc_handlers: RpcHandlers,
 }
 
+/// Hardware benchmark scores of a provider resource.
+pub fn hardware_scores(hwbench: &sc_sysinfo::HwBench) -> HardwareScores {
+	HardwareScores {
+		cpu_hashrate_score: hwbench.cpu_hashrate_score,
+		memory_memcpy_score: hwbench.memory_memcpy_score,
+		disk_sequential_write_score: hwbench.disk_sequential_write_score,
+		disk_random_write_score: hwbench.disk_random_write_score,
+	}
+}
+
+/// Keep the peer id of the node and its latest hardware benchmark scores in the offchain
+/// storage, where the offchain workers of the provider pallet read them to attest the resources
+/// run by the node.
+///
+/// The benchmarks run again every [`PROVIDER_HWBENCH_INTERVAL`] and block while they do, so this
+/// must be spawned as a blocking task.
+async fn store_hwbench(
+	mut storage: impl OffchainStorage,
+	peer_id: String,
+	mut hwbench: sc_sysinfo::HwBench,
+	database_path: PathBuf,
+) {
+	storage.set(STORAGE_PREFIX, PEER_ID_STORAGE_KEY, &peer_id.into_bytes().encode());
+	loop {
+		storage.set(STORAGE_PREFIX, HWBENCH_STORAGE_KEY, &hardware_scores(&hwbench).encode());
+		futures_timer::Delay::new(PROVIDER_HWBENCH_INTERVAL).await;
+		hwbench = sc_sysinfo::gather_hwbench(Some(&database_path));
+	}
+}
+
 /// Creates a full service from the configuration.
 pub fn new_full_base(
 	mut config: Configuration,
@@ -380,6 +417,10 @@ pub fn new_full_base(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let offchain_worker_enabled = config.offchain_worker.enabled;
+	let offchain_storage = backend.offchain_storage();
+	let database_path = config.database.path().map(PathBuf::from);
+
 	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
 		backend,
@@ -396,6 +437,21 @@ pub fn new_full_base(
 	if let Some(hwbench) = hwbench {
 		sc_sysinfo::print_hwbench(&hwbench);
 
+		if let (true, Some(storage), Some(database_path)) =
+			(offchain_worker_enabled, offchain_storage, database_path)
+		{
+			task_manager.spawn_handle().spawn_blocking(
+				"provider-hwbench",
+				None,
+				store_hwbench(
+					storage,
+					network.local_peer_id().to_base58(),
+					hwbench.clone(),
+					database_path,
+				),
+			);
+		}
+
 		if let Some(ref mut telemetry) = telemetry {
 			let telemetry_handle = telemetry.handle();
 			task_manager.spawn_handle().spawn(
@@ -51,6 +51,10 @@ use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use pallet_provider_rpc_runtime_api::{
+	ComputingResource, ProviderPoints, Reputation, ResourceDemand, ResourceStatus,
+	ResourceStringLimit, Specification,
+};
 use pallet_session::historical::{self as pallet_session_historical};
 pub use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
@@ -80,6 +84,8 @@ pub use frame_system::Call as SystemCall;
 #[cfg(any(feature = "std", test))]
 pub use pallet_balances::Call as BalancesCall;
 #[cfg(any(feature = "std", test))]
+pub use pallet_provider::Call as ProviderCall;
+#[cfg(any(feature = "std", test))]
 pub use pallet_staking::StakerStatus;
 #[cfg(any(feature = "std", test))]
 pub use pallet_sudo::Call as SudoCall;
@@ -122,10 +128,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 269,
+	spec_version: 270,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	state_version: 1,
 };
 
@@ -1407,11 +1413,121 @@ impl pallet_state_trie_migration::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxExpiriesPerBlock: u32 = 64;
+	pub const ProviderHeartbeatInterval: BlockNumber = 10 * MINUTES;
+	pub const MaxMissedHeartbeats: u32 = 3;
+	pub const MaxHeartbeatChecksPerBlock: u32 = 64;
+	pub const ProviderUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const ProviderAttestationInterval: BlockNumber = 1 * DAYS;
+	pub const ProviderAuditPeriod: BlockNumber = EPOCH_DURATION_IN_BLOCKS;
+	pub const ProviderAuditSampleSize: u32 = 16;
+	pub const ProviderChallengeDeadline: BlockNumber = 10 * MINUTES;
+	pub const BondPerCpu: Balance = 10 * DOLLARS;
+	pub const BondPerMemory: Balance = 1 * DOLLARS;
+	pub const BondPerStorage: Balance = 10 * CENTS;
+	pub const BondPerBandwidth: Balance = 1 * CENTS;
+	pub const BondPerAccelerator: Balance = 100 * DOLLARS;
+	pub const OfflineSlashRatio: Perbill = Perbill::from_percent(10);
+	pub const ReservationDeposit: Perbill = Perbill::from_percent(20);
+	pub const ProviderUnbondingPeriod: BlockNumber = 7 * DAYS;
+	pub const ProviderEraDuration: BlockNumber = 1 * DAYS;
+	pub const ProviderEraRewardPot: Balance = 1_000 * DOLLARS;
+	pub ProviderRewardPotAccount: Option<AccountId> = Some(Treasury::account_id());
+	pub const ProviderRewardHistoryDepth: u32 = 84;
+	pub const ProviderReputationDecay: Perbill = Perbill::from_percent(90);
+	pub const ProviderReputationFaultPenalty: u32 = 1 * HOURS;
+	pub const MaxMatchCandidates: u32 = 256;
+	pub const MaxResourcesPerProvider: u32 = 128;
+	pub const MaxOrdersPerRenter: u32 = 128;
+	pub const MaxLeasesPerResource: u32 = 16;
+	pub const MaxReservationsPerResource: u32 = 16;
+	pub const MaxBidsPerAuction: u32 = 64;
+	pub const MaxAcceptedAssets: u32 = 16;
+	pub const ProviderPalletId: PalletId = PalletId(*b"py/prvdr");
+	pub const ProviderPriceAdjustmentPeriod: BlockNumber = EPOCH_DURATION_IN_BLOCKS;
+	pub const ProviderMaxPriceAdjustmentsPerBlock: u32 = 64;
+	pub const ProviderTargetUtilization: Perbill = Perbill::from_percent(75);
+	pub ProviderPriceAdjustmentVariability: Multiplier = Multiplier::saturating_from_rational(1, 10);
+	pub ProviderMinimumPriceMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+	pub ProviderMaximumPriceMultiplier: Multiplier = Multiplier::saturating_from_integer(10);
+	pub const MaxUnbondingChunks: u32 = 32;
+}
+
 impl pallet_provider::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type BalanceToNumber = ConvertInto;
 	type NumberToBalance = ConvertInto;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = ProviderPalletId;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type AuthorityId = pallet_provider::crypto::ProviderAuthId;
+	type HeartbeatInterval = ProviderHeartbeatInterval;
+	type MaxMissedHeartbeats = MaxMissedHeartbeats;
+	type MaxHeartbeatChecksPerBlock = MaxHeartbeatChecksPerBlock;
+	type UnsignedPriority = ProviderUnsignedPriority;
+	type AttestationInterval = ProviderAttestationInterval;
+	type UnixTime = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type Call = Call;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type AuditPeriod = ProviderAuditPeriod;
+	type AuditSampleSize = ProviderAuditSampleSize;
+	type ChallengeDeadline = ProviderChallengeDeadline;
+	type PriceAdjustmentPeriod = ProviderPriceAdjustmentPeriod;
+	type MaxPriceAdjustmentsPerBlock = ProviderMaxPriceAdjustmentsPerBlock;
+	type TargetUtilization = ProviderTargetUtilization;
+	type PriceAdjustmentVariability = ProviderPriceAdjustmentVariability;
+	type MinimumPriceMultiplier = ProviderMinimumPriceMultiplier;
+	type MaximumPriceMultiplier = ProviderMaximumPriceMultiplier;
+	type BondPerCpu = BondPerCpu;
+	type BondPerMemory = BondPerMemory;
+	type BondPerStorage = BondPerStorage;
+	type BondPerBandwidth = BondPerBandwidth;
+	type BondPerAccelerator = BondPerAccelerator;
+	type OfflineSlashRatio = OfflineSlashRatio;
+	type ReservationDeposit = ReservationDeposit;
+	type UnbondingPeriod = ProviderUnbondingPeriod;
+	type EraDuration = ProviderEraDuration;
+	type EraRewardPot = ProviderEraRewardPot;
+	type RewardPotAccount = ProviderRewardPotAccount;
+	type RewardHistoryDepth = ProviderRewardHistoryDepth;
+	type ReputationDecay = ProviderReputationDecay;
+	type ReputationFaultPenalty = ProviderReputationFaultPenalty;
+	type MaxMatchCandidates = MaxMatchCandidates;
+	type StringLimit = ResourceStringLimit;
+	type MaxResourcesPerProvider = MaxResourcesPerProvider;
+	type MaxOrdersPerRenter = MaxOrdersPerRenter;
+	type MaxLeasesPerResource = MaxLeasesPerResource;
+	type MaxReservationsPerResource = MaxReservationsPerResource;
+	type MaxBidsPerAuction = MaxBidsPerAuction;
+	type MaxAcceptedAssets = MaxAcceptedAssets;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type ThresholdOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_provider::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ProviderBenchmarkHelper;
+}
+
+/// creates the assets the benchmarked rentals of the provider pallet are paid in
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ProviderBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_provider::BenchmarkHelper<u32, AccountId> for ProviderBenchmarkHelper {
+	fn asset(i: u32) -> u32 {
+		i
+	}
+
+	fn fund(asset: u32, who: &AccountId) {
+		use frame_support::traits::tokens::fungibles::{Create, Mutate};
+		// the asset already exists if it has been funded before
+		let _ = <Assets as Create<AccountId>>::create(asset, who.clone(), true, 1);
+		let _ = <Assets as Mutate<AccountId>>::mint_into(asset, who, u64::MAX / 2);
+	}
 }
 
 construct_runtime!(
@@ -1512,7 +1628,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	pallet_bags_list::migrations::CheckCounterPrefix<Runtime>,
+	(
+		pallet_bags_list::migrations::CheckCounterPrefix<Runtime>,
+		pallet_provider::migrations::v1::MigrateToV1<Runtime>,
+	),
 >;
 
 /// MMR helper types.
@@ -1557,6 +1676,7 @@ mod benches {
 		[pallet_multisig, Multisig]
 		[pallet_offences, OffencesBench::<Runtime>]
 		[pallet_preimage, Preimage]
+		[pallet_provider, Provider]
 		[pallet_proxy, Proxy]
 		[pallet_referenda, Referenda]
 		[pallet_remark, Remark]
@@ -1790,6 +1910,58 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_provider_rpc_runtime_api::ProviderApi<
+		Block,
+		AccountId,
+		BlockNumber,
+		Balance,
+	> for Runtime {
+		fn resources(
+			status: Option<ResourceStatus>,
+			specification: Option<Specification>,
+			owner: Option<AccountId>,
+		) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>> {
+			Provider::resources_by(status, specification, owner)
+		}
+
+		fn resources_matching(
+			demand: ResourceDemand<ResourceStringLimit>,
+			status: Option<ResourceStatus>,
+		) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>> {
+			Provider::resources_matching(&demand, status)
+		}
+
+		fn provider_resources(who: AccountId) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>> {
+			Provider::provider_resources(&who)
+		}
+
+		fn provider_points(who: AccountId) -> Option<ProviderPoints> {
+			Provider::provider_points(who)
+		}
+
+		fn rental_quote(index: u64, duration: BlockNumber) -> Option<Balance> {
+			Provider::rental_quote(index, duration)
+		}
+
+		fn unit_price_quote(index: u64) -> Option<Balance> {
+			Provider::unit_price_quote(index)
+		}
+
+		fn resource_reputation(index: u64) -> Option<Reputation> {
+			Provider::current_reputation(index)
+		}
+
+		fn provider_reputation(who: AccountId) -> Option<Perbill> {
+			Provider::provider_reputation(&who)
+		}
+
+		fn resources_by_reputation(
+			min_score: Perbill,
+		) -> Vec<(ComputingResource<BlockNumber, AccountId, ResourceStringLimit>, Reputation)> {
+			Provider::resources_by_reputation(min_score)
+		}
+	}
+
 	impl pallet_mmr::primitives::MmrApi<
 		Block,
 		mmr::Hash,
@@ -95,6 +95,7 @@ pub fn config_endowed(code: Option<&[u8]>, extra_endowed: Vec<AccountId>) -> Gen
 		provider: ProviderConfig {
 			resource: Default::default(),
 			resource_index: Default::default(),
+			specification_thresholds: Default::default(),
 		},
 	}
 }
@@ -0,0 +1,69 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the provider pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use frame_support::Parameter;
+use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeDisplay};
+pub use sp_runtime::Perbill;
+use sp_std::vec::Vec;
+
+pub use sp_hamster::p_provider::{
+	ComputingResource, ProviderPoints, Reputation, ResourceDemand, ResourceStatus,
+	ResourceStringLimit, Specification,
+};
+
+sp_api::decl_runtime_apis! {
+	#[api_version(4)]
+	pub trait ProviderApi<AccountId, BlockNumber, Balance> where
+		AccountId: Parameter,
+		BlockNumber: Parameter + AtLeast32BitUnsigned,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// resources matching every given filter
+		fn resources(
+			status: Option<ResourceStatus>,
+			specification: Option<Specification>,
+			owner: Option<AccountId>,
+		) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>;
+		/// resources with `status`, if given, satisfying every requirement of a demand
+		fn resources_matching(
+			demand: ResourceDemand<ResourceStringLimit>,
+			status: Option<ResourceStatus>,
+		) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>;
+		/// resources registered by a provider
+		fn provider_resources(who: AccountId) -> Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>;
+		/// points of a provider
+		fn provider_points(who: AccountId) -> Option<ProviderPoints>;
+		/// price of renting a resource for `duration` blocks
+		fn rental_quote(index: u64, duration: BlockNumber) -> Option<Balance>;
+		/// current unit price of a resource, following the multiplier of its class if it is
+		/// dynamically priced
+		fn unit_price_quote(index: u64) -> Option<Balance>;
+		/// current reputation of a resource
+		fn resource_reputation(index: u64) -> Option<Reputation>;
+		/// mean reputation score of the resources of a provider
+		fn provider_reputation(who: AccountId) -> Option<Perbill>;
+		/// resources scoring at least `min_score`, best first
+		fn resources_by_reputation(
+			min_score: Perbill,
+		) -> Vec<(ComputingResource<BlockNumber, AccountId, ResourceStringLimit>, Reputation)>;
+	}
+}
@@ -0,0 +1,314 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the provider pallet.
+
+pub use self::gen_client::Client as ProviderClient;
+use codec::Codec;
+use frame_support::Parameter;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_provider_rpc_runtime_api::ProviderApi as ProviderRuntimeApi;
+use pallet_provider_rpc_runtime_api::{
+	ComputingResource, Perbill, ProviderPoints, Reputation, ResourceDemand, ResourceStatus,
+	ResourceStringLimit, Specification,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{AtLeast32BitUnsigned, Block as BlockT, MaybeDisplay},
+};
+use std::sync::Arc;
+
+#[rpc]
+pub trait ProviderApi<BlockHash, AccountId, BlockNumber, Balance, ResourceResponse> {
+	/// Returns the resources matching every given filter.
+	#[rpc(name = "provider_resources")]
+	fn resources(
+		&self,
+		status: Option<ResourceStatus>,
+		specification: Option<Specification>,
+		owner: Option<AccountId>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ResourceResponse>>;
+
+	/// Returns the resources with the given status, if any, satisfying every requirement of a
+	/// demand, including its storage, bandwidth and accelerator dimensions.
+	#[rpc(name = "provider_resourcesMatching")]
+	fn resources_matching(
+		&self,
+		demand: ResourceDemand<ResourceStringLimit>,
+		status: Option<ResourceStatus>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ResourceResponse>>;
+
+	/// Returns the resources registered by a provider.
+	#[rpc(name = "provider_providerResources")]
+	fn provider_resources(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ResourceResponse>>;
+
+	/// Returns the points of a provider, or `None` if it has none.
+	#[rpc(name = "provider_points")]
+	fn provider_points(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ProviderPoints>>;
+
+	/// Returns the price of renting a resource for `duration` blocks, or `None` if the resource
+	/// does not exist.
+	#[rpc(name = "provider_rentalQuote")]
+	fn rental_quote(
+		&self,
+		index: u64,
+		duration: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Option<NumberOrHex>>;
+
+	/// Returns the current unit price of a resource, which follows the price multiplier of its
+	/// class if it is dynamically priced, or `None` if the resource does not exist.
+	#[rpc(name = "provider_unitPriceQuote")]
+	fn unit_price_quote(&self, index: u64, at: Option<BlockHash>) -> Result<Option<NumberOrHex>>;
+
+	/// Returns the current reputation of a resource, or `None` if the resource does not exist.
+	#[rpc(name = "provider_resourceReputation")]
+	fn resource_reputation(&self, index: u64, at: Option<BlockHash>) -> Result<Option<Reputation>>;
+
+	/// Returns the mean reputation score of the resources of a provider, or `None` if it has no
+	/// resources.
+	#[rpc(name = "provider_providerReputation")]
+	fn provider_reputation(&self, who: AccountId, at: Option<BlockHash>)
+		-> Result<Option<Perbill>>;
+
+	/// Returns the resources scoring at least `min_score` with their reputation, best first.
+	#[rpc(name = "provider_resourcesByReputation")]
+	fn resources_by_reputation(
+		&self,
+		min_score: Perbill,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(ResourceResponse, Reputation)>>;
+}
+
+/// A struct that implements the [`ProviderApi`].
+pub struct Provider<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Provider<C, P> {
+	/// Create new `Provider` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Display) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: message.into(),
+		data: Some(e.to_string().into()),
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance>
+	ProviderApi<
+		<Block as BlockT>::Hash,
+		AccountId,
+		BlockNumber,
+		Balance,
+		ComputingResource<BlockNumber, AccountId, ResourceStringLimit>,
+	> for Provider<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: ProviderRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+	AccountId: Parameter,
+	BlockNumber: Parameter + AtLeast32BitUnsigned,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	fn resources(
+		&self,
+		status: Option<ResourceStatus>,
+		specification: Option<Specification>,
+		owner: Option<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.resources(&at, status, specification, owner)
+			.map_err(|e| runtime_error("Unable to query resources.", e))
+	}
+
+	fn resources_matching(
+		&self,
+		demand: ResourceDemand<ResourceStringLimit>,
+		status: Option<ResourceStatus>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.resources_matching(&at, demand, status)
+			.map_err(|e| runtime_error("Unable to query matching resources.", e))
+	}
+
+	fn provider_resources(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ComputingResource<BlockNumber, AccountId, ResourceStringLimit>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.provider_resources(&at, who)
+			.map_err(|e| runtime_error("Unable to query provider resources.", e))
+	}
+
+	fn provider_points(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProviderPoints>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.provider_points(&at, who)
+			.map_err(|e| runtime_error("Unable to query provider points.", e))
+	}
+
+	fn rental_quote(
+		&self,
+		index: u64,
+		duration: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let quote = api
+			.rental_quote(&at, index, duration)
+			.map_err(|e| runtime_error("Unable to query rental quote.", e))?;
+
+		quote
+			.map(|price| {
+				price.try_into().map_err(|_| RpcError {
+					code: ErrorCode::InvalidParams,
+					message: format!("{} doesn't fit in NumberOrHex representation", price),
+					data: None,
+				})
+			})
+			.transpose()
+	}
+
+	fn unit_price_quote(
+		&self,
+		index: u64,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let quote = api
+			.unit_price_quote(&at, index)
+			.map_err(|e| runtime_error("Unable to query unit price quote.", e))?;
+
+		quote
+			.map(|price| {
+				price.try_into().map_err(|_| RpcError {
+					code: ErrorCode::InvalidParams,
+					message: format!("{} doesn't fit in NumberOrHex representation", price),
+					data: None,
+				})
+			})
+			.transpose()
+	}
+
+	fn resource_reputation(
+		&self,
+		index: u64,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Reputation>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.resource_reputation(&at, index)
+			.map_err(|e| runtime_error("Unable to query resource reputation.", e))
+	}
+
+	fn provider_reputation(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Perbill>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.provider_reputation(&at, who)
+			.map_err(|e| runtime_error("Unable to query provider reputation.", e))
+	}
+
+	fn resources_by_reputation(
+		&self,
+		min_score: Perbill,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(ComputingResource<BlockNumber, AccountId, ResourceStringLimit>, Reputation)>>
+	{
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.resources_by_reputation(&at, min_score)
+			.map_err(|e| runtime_error("Unable to query resources by reputation.", e))
+	}
+}
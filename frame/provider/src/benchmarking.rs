@@ -0,0 +1,969 @@
+//! Provider pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
+use frame_system::RawOrigin;
+use sp_hamster::p_provider::{MaxResourceDimensions, ResourceDimension, ResourceDimensions};
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+use crate::Pallet as Provider;
+
+const SEED: u32 = 0;
+const CPU: u64 = 8;
+const MEMORY: u64 = 16;
+const UNIT_PRICE: u128 = 10;
+const RENTABLE_DURATION: u32 = 1_000_000;
+const RENT_DURATION: u32 = 100;
+const POINTS: u64 = 100;
+const SCORE: u64 = 1_000;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	fund::<T>(&who);
+	who
+}
+
+/// byte string of the maximum length, starting with the encoding of `seed`
+fn string<T: Config>(seed: u64) -> BoundedStringOf<T> {
+	let mut bytes = seed.encode();
+	bytes.resize(T::StringLimit::get() as usize, b'x');
+	bytes.try_into().expect("resized to the bound; qed")
+}
+
+/// the maximum number of dimensions, all accelerators of different models
+fn dimensions<T: Config>() -> ResourceDimensions<T::StringLimit> {
+	(0..MaxResourceDimensions::get() as u64)
+		.map(|i| ResourceDimension::Accelerator { model: string::<T>(i), count: 1, memory: MEMORY })
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("as many dimensions as the bound; qed")
+}
+
+fn scores() -> HardwareScores {
+	HardwareScores {
+		cpu_hashrate_score: SCORE,
+		memory_memcpy_score: SCORE,
+		disk_sequential_write_score: Some(SCORE),
+		disk_random_write_score: Some(SCORE),
+	}
+}
+
+/// thresholds of every specification, resources with `scores()` only reach `General`, after
+/// checking all of them
+fn set_thresholds<T: Config>() {
+	let threshold = |score| SpecificationThreshold {
+		cpu: CPU,
+		memory: MEMORY,
+		cpu_hashrate_score: score,
+		memory_memcpy_score: score,
+		disk_sequential_write_score: score,
+		disk_random_write_score: score,
+	};
+	SpecificationThresholds::<T>::insert(Specification::General, threshold(SCORE));
+	SpecificationThresholds::<T>::insert(Specification::Enhanced, threshold(SCORE + 1));
+	SpecificationThresholds::<T>::insert(Specification::HighRanking, threshold(SCORE + 2));
+}
+
+/// register a resource of a funded `provider`, which can be paid for its leases
+fn create_resource<T: Config>(provider: &T::AccountId) -> u64 {
+	fund::<T>(provider);
+	let index = Provider::<T>::resource_index();
+	Provider::<T>::do_register_resource(
+		provider.clone(),
+		string::<T>(index),
+		string::<T>(index),
+		ResourceConfig::new(CPU, MEMORY, string::<T>(0), string::<T>(0), dimensions::<T>()),
+		scores(),
+		UNIT_PRICE,
+		RENTABLE_DURATION.into(),
+	)
+	.expect("the resource can be registered")
+}
+
+fn rent<T: Config>(renter: &T::AccountId, index: u64, duration: T::BlockNumber) -> u64 {
+	let resource = Provider::<T>::resource(index).expect("the resource exists");
+	let slice = resource.capacity();
+	let rent_unit_price = resource.rental_info.rent_unit_price;
+	Provider::<T>::do_rent_resource(
+		renter.clone(),
+		resource,
+		slice,
+		rent_unit_price,
+		duration,
+		None,
+	)
+	.expect("the resource can be rented")
+}
+
+/// make the resource divisible and lease `leases` slices of one memory to different renters
+fn rent_slices<T: Config>(index: u64, leases: u32, duration: T::BlockNumber) {
+	Resources::<T>::mutate(index, |resource| {
+		if let Some(resource) = resource {
+			resource.divisible = true;
+		}
+	});
+	for i in 0..leases {
+		let resource = Provider::<T>::resource(index).expect("the resource exists");
+		let renter = funded_account::<T>("renter", index as u32 * leases + i);
+		let slice = ResourceSlice::new(0, 1);
+		let rent_unit_price = Provider::<T>::slice_unit_price(&resource, &slice);
+		Provider::<T>::do_rent_resource(renter, resource, slice, rent_unit_price, duration, None)
+			.expect("the slice can be rented");
+	}
+}
+
+/// number of slices leased from a resource with the most leases
+fn max_leases<T: Config>() -> u32 {
+	T::MaxLeasesPerResource::get().min(MEMORY as u32)
+}
+
+/// fill the queues of the blocks following `from`, so that queueing at `from` probes every
+/// block before finding room
+fn fill_queues<T: Config, Queue, Bound>(from: T::BlockNumber)
+where
+	Queue: frame_support::StorageMap<
+		T::BlockNumber,
+		BoundedVec<u64, Bound>,
+		Query = BoundedVec<u64, Bound>,
+	>,
+	Bound: Get<u32>,
+{
+	let full: BoundedVec<u64, Bound> = vec![u64::MAX; Bound::get() as usize]
+		.try_into()
+		.expect("filled to the bound; qed");
+	let mut block = from;
+	for _ in 1..MAX_QUEUE_PROBES {
+		Queue::insert(block, full.clone());
+		block = block.saturating_add(One::one());
+	}
+}
+
+/// fill the order list of `renter`, with `order_index` last
+fn fill_renter_orders<T: Config>(renter: &T::AccountId, order_index: u64) {
+	let mut indexes: Vec<u64> =
+		(1..T::MaxOrdersPerRenter::get()).map(|i| u64::MAX - i as u64).collect();
+	indexes.push(order_index);
+	let indexes: BoundedVec<u64, T::MaxOrdersPerRenter> =
+		indexes.try_into().expect("filled to the bound; qed");
+	RenterOrders::<T>::insert(renter, indexes);
+}
+
+/// list a resource of a new provider in an auction of a lease of `RENT_DURATION` blocks,
+/// closing at `close_at`
+fn create_auction<T: Config>(kind: AuctionKind<T::BlockNumber>, close_at: T::BlockNumber) -> u64 {
+	let provider: T::AccountId = account("provider", 0, SEED);
+	let index = create_resource::<T>(&provider);
+	let auction_index = Provider::<T>::auction_index();
+	Provider::<T>::list_auction(
+		RawOrigin::Signed(provider).into(),
+		index,
+		kind,
+		T::NumberToBalance::convert(UNIT_PRICE),
+		RENT_DURATION.into(),
+		close_at,
+	)
+	.expect("the resource can be auctioned");
+	auction_index
+}
+
+/// commit a sealed bid of `unit_price`, with a deposit covering the price of the lease
+fn commit_sealed_bid<T: Config>(bidder: &T::AccountId, auction_index: u64, unit_price: u128) {
+	let unit_price = T::NumberToBalance::convert(unit_price);
+	let commitment = Provider::<T>::bid_commitment(bidder, unit_price, &Default::default());
+	let deposit = T::NumberToBalance::convert((UNIT_PRICE + 1) * RENT_DURATION as u128);
+	Provider::<T>::commit_bid(
+		RawOrigin::Signed(bidder.clone()).into(),
+		auction_index,
+		commitment,
+		deposit,
+	)
+	.expect("the bid can be committed");
+}
+
+benchmarks! {
+	register_resource {
+		let caller: T::AccountId = whitelisted_caller();
+		let index = Provider::<T>::resource_index();
+		set_thresholds::<T>();
+		let config =
+			ResourceConfig::new(CPU, MEMORY, string::<T>(0), string::<T>(0), dimensions::<T>());
+		let price = T::NumberToBalance::convert(UNIT_PRICE);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		string::<T>(index),
+		string::<T>(index),
+		config,
+		scores(),
+		price,
+		RENTABLE_DURATION.into()
+	)
+	verify {
+		assert_last_event::<T>(
+			Event::ResourceRegistered { who: caller, index, peer_id: string::<T>(index) }.into(),
+		);
+	}
+
+	update_resource {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let index = create_resource::<T>(&caller);
+		// the bond is topped up to the larger configuration
+		Provider::<T>::bond_resource(RawOrigin::Signed(caller.clone()).into(), index)?;
+		set_thresholds::<T>();
+		let config = ResourceConfig::new(
+			CPU * 2,
+			MEMORY * 2,
+			string::<T>(1),
+			string::<T>(1),
+			dimensions::<T>(),
+		);
+		let price = T::NumberToBalance::convert(UNIT_PRICE * 2);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		index,
+		Some(string::<T>(u64::MAX)),
+		Some(config),
+		Some(scores()),
+		Some(price)
+	)
+	verify {
+		assert_last_event::<T>(Event::ResourceUpdated { who: caller, index }.into());
+	}
+
+	add_resource_duration {
+		let caller: T::AccountId = whitelisted_caller();
+		let index = create_resource::<T>(&caller);
+		let duration: T::BlockNumber = RENTABLE_DURATION.into();
+	}: _(RawOrigin::Signed(caller.clone()), index, duration)
+	verify {
+		assert_last_event::<T>(Event::ResourceDurationAdded { who: caller, index, duration }.into());
+	}
+
+	remove_resource {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let index = create_resource::<T>(&caller);
+		Provider::<T>::bond_resource(RawOrigin::Signed(caller.clone()).into(), index)?;
+		// the resource is the last one of a full resource list
+		let mut indexes: Vec<u64> =
+			(1..T::MaxResourcesPerProvider::get()).map(|i| u64::MAX - i as u64).collect();
+		indexes.push(index);
+		let indexes: BoundedVec<u64, T::MaxResourcesPerProvider> =
+			indexes.try_into().expect("filled to the bound; qed");
+		Providers::<T>::insert(&caller, indexes);
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert_last_event::<T>(Event::ResourceRemoved { who: caller, index }.into());
+	}
+
+	bond_resource {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let index = create_resource::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert!(ResourceBonds::<T>::contains_key(index));
+	}
+
+	unbond_resource {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let index = create_resource::<T>(&caller);
+		Provider::<T>::bond_resource(RawOrigin::Signed(caller.clone()).into(), index)?;
+	}: _(RawOrigin::Signed(caller.clone()), index)
+	verify {
+		assert!(!ResourceBonds::<T>::contains_key(index));
+		assert!(Unbonding::<T>::contains_key(&caller));
+	}
+
+	withdraw_unbonded {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		// every chunk of a full unbonding queue can be withdrawn
+		let chunk: BalanceOf<T> = 1_000u32.into();
+		let chunks: BoundedVec<_, T::MaxUnbondingChunks> =
+			vec![(T::BlockNumber::zero(), chunk); T::MaxUnbondingChunks::get() as usize]
+				.try_into()
+				.expect("filled to the bound; qed");
+		T::Currency::reserve(&caller, chunk.saturating_mul((chunks.len() as u32).into()))?;
+		Unbonding::<T>::insert(&caller, chunks);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Unbonding::<T>::contains_key(&caller));
+	}
+
+	payout_provider {
+		let caller: T::AccountId = whitelisted_caller();
+		let provider: T::AccountId = account("provider", 0, SEED);
+		if let Some(pot) = T::RewardPotAccount::get() {
+			fund::<T>(&pot);
+		}
+		// the oldest claimable era is claimed last, after a checkpoint in every era
+		let depth = T::RewardHistoryDepth::get();
+		for era in 0 ..= depth {
+			CurrentEra::<T>::put(era);
+			Provider::<T>::add_provider_points(&provider, POINTS, 0);
+		}
+		let claimed: Vec<EraIndex> = (2 ..= depth).collect();
+		let claimed: BoundedVec<EraIndex, T::RewardHistoryDepth> =
+			claimed.try_into().expect("one less than the bound; qed");
+		ClaimedEras::<T>::insert(&provider, claimed);
+		let era: EraIndex = 1;
+		CurrentEra::<T>::put(depth + 1);
+		ErasTotalPoints::<T>::insert(era, POINTS as u128 * 2);
+		ErasRewardPot::<T>::insert(era, T::EraRewardPot::get());
+	}: _(RawOrigin::Signed(caller), provider.clone(), era)
+	verify {
+		assert!(Provider::<T>::claimed_eras(&provider).contains(&era));
+	}
+
+	rent_resource {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let now = frame_system::Pallet::<T>::block_number();
+		fill_queues::<T, OrderExpiries<T>, _>(now.saturating_add(duration));
+	}: _(RawOrigin::Signed(caller), index, duration)
+	verify {
+		assert!(ResourceLeases::<T>::contains_key(index));
+	}
+
+	rent_resource_in_asset {
+		// the asset is the last one accepted by the resource
+		let provider: T::AccountId = account("provider", 0, SEED);
+		let index = create_resource::<T>(&provider);
+		let accepted = T::MaxAcceptedAssets::get();
+		let prices = (0 .. accepted)
+			.map(|i| (T::BenchmarkHelper::asset(i), (UNIT_PRICE as u32).into()))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("as many prices as accepted assets");
+		Provider::<T>::set_asset_prices(RawOrigin::Signed(provider).into(), index, prices)?;
+		let asset_id = T::BenchmarkHelper::asset(accepted - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		T::BenchmarkHelper::fund(asset_id, &caller);
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let now = frame_system::Pallet::<T>::block_number();
+		fill_queues::<T, OrderExpiries<T>, _>(now.saturating_add(duration));
+	}: _(RawOrigin::Signed(caller), index, asset_id, duration)
+	verify {
+		assert_eq!(Provider::<T>::order_asset(Provider::<T>::order_index() - 1), Some(asset_id));
+	}
+
+	set_dynamic_pricing {
+		let caller: T::AccountId = whitelisted_caller();
+		let index = create_resource::<T>(&caller);
+		let bounds = Some((1u32.into(), BalanceOf::<T>::max_value()));
+	}: _(RawOrigin::Signed(caller), index, bounds)
+	verify {
+		assert!(DynamicPrices::<T>::contains_key(index));
+	}
+
+	set_asset_prices {
+		let a in 1 .. T::MaxAcceptedAssets::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let index = create_resource::<T>(&caller);
+		let prices: BoundedVec<_, _> = (0 .. a)
+			.map(|i| (T::BenchmarkHelper::asset(i), (UNIT_PRICE as u32).into()))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("as many prices as accepted assets");
+	}: _(RawOrigin::Signed(caller), index, prices)
+	verify {
+		assert_eq!(Provider::<T>::asset_prices(index).len(), a as usize);
+	}
+
+	set_resource_divisible {
+		let caller: T::AccountId = whitelisted_caller();
+		let index = create_resource::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), index, true)
+	verify {
+		assert!(Provider::<T>::resource(index).expect("the resource exists").divisible);
+	}
+
+	rent_slice {
+		// the slice takes what is left of a resource with one lease less than the bound
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		rent_slices::<T>(index, max_leases::<T>() - 1, duration);
+		let slice = Provider::<T>::resource(index).expect("the resource exists").available();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let now = frame_system::Pallet::<T>::block_number();
+		fill_queues::<T, OrderExpiries<T>, _>(now.saturating_add(duration));
+	}: _(RawOrigin::Signed(caller), index, slice, duration)
+	verify {
+		assert_eq!(
+			Provider::<T>::resource(index).expect("the resource exists").status,
+			ResourceStatus::Inuse
+		);
+	}
+
+	rent_by_demand {
+		let c in 1 .. T::MaxMatchCandidates::get();
+
+		// every candidate satisfies the demand, with every demanded dimension compared to all of
+		// its own, and is compared to the others
+		for i in 0 .. c {
+			create_resource::<T>(&account("provider", i, SEED));
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let demand = ResourceDemand::new(
+			CPU,
+			MEMORY,
+			Specification::General,
+			Some(string::<T>(0)),
+			Some(string::<T>(0)),
+			dimensions::<T>(),
+		);
+		let max_unit_price = T::NumberToBalance::convert(UNIT_PRICE);
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let now = frame_system::Pallet::<T>::block_number();
+		fill_queues::<T, OrderExpiries<T>, _>(now.saturating_add(duration));
+	}: _(RawOrigin::Signed(caller), demand, max_unit_price, duration, MatchPolicy::BestReputation)
+	verify {
+		assert_eq!(ResourceLeases::<T>::iter().count(), 1);
+	}
+
+	settle_order {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let renter = funded_account::<T>("renter", 0);
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let order_index = rent::<T>(&renter, index, duration);
+		fill_renter_orders::<T>(&renter, order_index);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(duration));
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), order_index)
+	verify {
+		assert!(!RentalOrders::<T>::contains_key(order_index));
+	}
+
+	renew_order {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let order_index = rent::<T>(&caller, index, duration);
+		let now = frame_system::Pallet::<T>::block_number();
+		let end_of_rent = now.saturating_add(duration).saturating_add(duration);
+		fill_queues::<T, OrderExpiries<T>, _>(end_of_rent);
+	}: _(RawOrigin::Signed(caller), order_index, duration)
+	verify {
+		let order = Provider::<T>::rental_order(order_index).expect("the order exists");
+		assert_eq!(order.rental_info.end_of_rent, end_of_rent);
+	}
+
+	terminate_order {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let order_index = rent::<T>(&caller, index, RENT_DURATION.into());
+		fill_renter_orders::<T>(&caller, order_index);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add((RENT_DURATION / 2).into()));
+	}: _(RawOrigin::Signed(caller), order_index)
+	verify {
+		assert!(!RentalOrders::<T>::contains_key(order_index));
+	}
+
+	reserve_resource {
+		// the leases of the resource are all read, and its reservations but one are booked
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		rent_slices::<T>(index, max_leases::<T>(), duration);
+		let mut start = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		let mut windows = Vec::new();
+		for _ in 1 .. T::MaxReservationsPerResource::get() {
+			windows.push((start, start.saturating_add(One::one())));
+			start = start.saturating_add(One::one());
+		}
+		let windows: BoundedVec<_, T::MaxReservationsPerResource> =
+			windows.try_into().expect("one less than the bound; qed");
+		ResourceReservations::<T>::insert(index, windows);
+		let end = start.saturating_add(duration);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), index, start, end)
+	verify {
+		assert!(Reservations::<T>::contains_key(0));
+	}
+
+	cancel_reservation {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		// the block number is raised to 1 after the setup on a fresh chain, the reservation must
+		// not have started by then
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		let end = start.saturating_add(duration);
+		Provider::<T>::reserve_resource(RawOrigin::Signed(caller.clone()).into(), index, start, end)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Reservations::<T>::contains_key(0));
+	}
+
+	activate_reservation {
+		// the most leases of the resource end when the reservation starts and are settled first
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		rent_slices::<T>(index, max_leases::<T>(), duration);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		let end = start.saturating_add(duration);
+		Provider::<T>::reserve_resource(RawOrigin::Signed(caller).into(), index, start, end)?;
+		frame_system::Pallet::<T>::set_block_number(start);
+		let now = frame_system::Pallet::<T>::block_number();
+		fill_queues::<T, OrderExpiries<T>, _>(now.saturating_add(duration));
+	}: _(RawOrigin::Root, 0)
+	verify {
+		assert_last_event::<T>(
+			Event::ReservationActivated { reservation_index: 0, order_index: max_leases::<T>() as u64 }
+				.into(),
+		);
+	}
+
+	list_auction {
+		// the reservations of the resource are all booked after the lease
+		let caller: T::AccountId = account("provider", 0, SEED);
+		let index = create_resource::<T>(&caller);
+		let now = frame_system::Pallet::<T>::block_number();
+		let lease_duration: T::BlockNumber = RENT_DURATION.into();
+		let close_at = now.saturating_add(lease_duration);
+		let mut start = close_at.saturating_add(lease_duration);
+		let mut windows = Vec::new();
+		for _ in 0 .. T::MaxReservationsPerResource::get() {
+			windows.push((start, start.saturating_add(One::one())));
+			start = start.saturating_add(One::one());
+		}
+		let windows: BoundedVec<_, T::MaxReservationsPerResource> =
+			windows.try_into().expect("as many windows as the bound; qed");
+		ResourceReservations::<T>::insert(index, windows);
+		// after the block the call runs at, which is 1 on a fresh chain
+		let kind = AuctionKind::SealedBid { reveal_start: now.saturating_add(2u32.into()) };
+		let reserve_price = T::NumberToBalance::convert(UNIT_PRICE);
+	}: _(RawOrigin::Signed(caller), index, kind, reserve_price, lease_duration, close_at)
+	verify {
+		assert!(Auctions::<T>::contains_key(0));
+	}
+
+	bid {
+		// the previous highest bidder is unreserved
+		let now = frame_system::Pallet::<T>::block_number();
+		let auction_index =
+			create_auction::<T>(AuctionKind::English, now.saturating_add(RENT_DURATION.into()));
+		let bidder = funded_account::<T>("bidder", 0);
+		let unit_price = T::NumberToBalance::convert(UNIT_PRICE);
+		Provider::<T>::bid(RawOrigin::Signed(bidder).into(), auction_index, unit_price)?;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let unit_price = T::NumberToBalance::convert(UNIT_PRICE + 1);
+	}: _(RawOrigin::Signed(caller.clone()), auction_index, unit_price)
+	verify {
+		let auction = Provider::<T>::auction(auction_index).expect("the auction exists");
+		assert_eq!(auction.best_bid, Some((caller, UNIT_PRICE + 1)));
+	}
+
+	commit_bid {
+		let now = frame_system::Pallet::<T>::block_number();
+		// after the block the call runs at, which is 1 on a fresh chain
+		let kind = AuctionKind::SealedBid { reveal_start: now.saturating_add(2u32.into()) };
+		let auction_index = create_auction::<T>(kind, now.saturating_add(RENT_DURATION.into()));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let unit_price = T::NumberToBalance::convert(UNIT_PRICE);
+		let commitment = Provider::<T>::bid_commitment(&caller, unit_price, &Default::default());
+		let deposit = T::NumberToBalance::convert(UNIT_PRICE * RENT_DURATION as u128);
+	}: _(RawOrigin::Signed(caller.clone()), auction_index, commitment, deposit)
+	verify {
+		assert!(AuctionBids::<T>::contains_key(auction_index, caller));
+	}
+
+	reveal_bid {
+		// the previous highest bidder is unreserved
+		let now = frame_system::Pallet::<T>::block_number();
+		let reveal_start = now.saturating_add(One::one());
+		let kind = AuctionKind::SealedBid { reveal_start };
+		let auction_index = create_auction::<T>(kind, now.saturating_add(RENT_DURATION.into()));
+		let bidder = funded_account::<T>("bidder", 0);
+		commit_sealed_bid::<T>(&bidder, auction_index, UNIT_PRICE);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		commit_sealed_bid::<T>(&caller, auction_index, UNIT_PRICE + 1);
+		frame_system::Pallet::<T>::set_block_number(reveal_start);
+		Provider::<T>::reveal_bid(
+			RawOrigin::Signed(bidder).into(),
+			auction_index,
+			T::NumberToBalance::convert(UNIT_PRICE),
+			Default::default(),
+		)?;
+		let unit_price = T::NumberToBalance::convert(UNIT_PRICE + 1);
+	}: _(RawOrigin::Signed(caller.clone()), auction_index, unit_price, Default::default())
+	verify {
+		let auction = Provider::<T>::auction(auction_index).expect("the auction exists");
+		assert_eq!(auction.best_bid, Some((caller, UNIT_PRICE + 1)));
+	}
+
+	close_auction {
+		// one of the `b` sealed bids is revealed and wins, the others are unreserved
+		let b in 1 .. T::MaxBidsPerAuction::get();
+		let now = frame_system::Pallet::<T>::block_number();
+		let reveal_start = now.saturating_add(One::one());
+		let close_at = reveal_start.saturating_add(One::one());
+		let auction_index = create_auction::<T>(AuctionKind::SealedBid { reveal_start }, close_at);
+		let winner: T::AccountId = whitelisted_caller();
+		fund::<T>(&winner);
+		commit_sealed_bid::<T>(&winner, auction_index, UNIT_PRICE);
+		for i in 1 .. b {
+			commit_sealed_bid::<T>(&funded_account::<T>("bidder", i), auction_index, UNIT_PRICE);
+		}
+		frame_system::Pallet::<T>::set_block_number(reveal_start);
+		Provider::<T>::reveal_bid(
+			RawOrigin::Signed(winner.clone()).into(),
+			auction_index,
+			T::NumberToBalance::convert(UNIT_PRICE),
+			Default::default(),
+		)?;
+		frame_system::Pallet::<T>::set_block_number(close_at);
+		fill_queues::<T, OrderExpiries<T>, _>(close_at.saturating_add(RENT_DURATION.into()));
+	}: _(RawOrigin::Root, auction_index)
+	verify {
+		assert_last_event::<T>(
+			Event::AuctionWon {
+				auction_index,
+				winner,
+				unit_price: T::NumberToBalance::convert(UNIT_PRICE),
+				order_index: 0,
+			}
+			.into(),
+		);
+	}
+
+	rate_order {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let order_index = rent::<T>(&caller, index, RENT_DURATION.into());
+	}: _(RawOrigin::Signed(caller), order_index, MAX_RATING)
+	verify {
+		assert_eq!(Provider::<T>::order_rating(order_index), Some(MAX_RATING));
+	}
+
+	refresh_reputation {
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		ResourceReputations::<T>::insert(index, Reputation::new(Zero::zero()));
+		Resources::<T>::mutate(index, |r| {
+			let statistics = &mut r.as_mut().expect("the resource exists").rental_statistics;
+			statistics.add_fault_count();
+			statistics.add_fault_duration(1);
+		});
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert_eq!(Provider::<T>::resource_reputation(index).expect("the reputation is stored").faulty, 1 + T::ReputationFaultPenalty::get() as u64);
+	}
+
+	claim_assets {
+		let asset_id = T::BenchmarkHelper::asset(0);
+		T::BenchmarkHelper::fund(asset_id, &Provider::<T>::escrow_account());
+		let caller: T::AccountId = whitelisted_caller();
+		UnclaimedAssets::<T>::insert(&caller, asset_id, UNIT_PRICE);
+	}: _(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert!(!UnclaimedAssets::<T>::contains_key(&caller, asset_id));
+	}
+
+	validate_unsigned_and_then_heartbeat {
+		let key =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+				Some(b"0x0505050505050505050505050505050505050505050505050505050505050505".to_vec()),
+			);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into();
+		let provider = public.clone().into_account();
+
+		// the resource went offline during a lease and comes back with the heartbeat
+		let index = create_resource::<T>(&provider);
+		let timeout = Provider::<T>::heartbeat_timeout();
+		rent::<T>(
+			&funded_account::<T>("renter", 0),
+			index,
+			timeout.saturating_add(RENT_DURATION.into()),
+		);
+		let now = frame_system::Pallet::<T>::block_number().saturating_add(timeout);
+		frame_system::Pallet::<T>::set_block_number(now);
+		HeartbeatCursor::<T>::put(now);
+		Provider::<T>::process_heartbeat_deadlines(now);
+
+		let heartbeat = HeartbeatPayload { block_number: now, resource_index: index, public };
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&heartbeat)
+			.ok_or("couldn't make signature")?;
+		let call = Call::<T>::heartbeat { heartbeat, signature };
+		let call_enc = call.encode();
+	}: {
+		Provider::<T>::validate_unsigned(TransactionSource::InBlock, &call)
+			.map_err(<&str>::from)?;
+		<Call<T> as Decode>::decode(&mut &*call_enc)
+			.expect("call is encoded above, encoding must be correct")
+			.dispatch_bypass_filter(RawOrigin::None.into())?;
+	}
+	verify {
+		assert_eq!(Provider::<T>::resource_heartbeat(index), Some(now));
+		assert_last_event::<T>(Event::HeartbeatReceived { index }.into());
+	}
+
+	validate_unsigned_and_then_attest_hardware {
+		let key =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+				Some(b"0x0505050505050505050505050505050505050505050505050505050505050505".to_vec()),
+			);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into();
+		let provider = public.clone().into_account();
+
+		// a flagged resource is unflagged and drops to `General`, after checking every threshold
+		let index = create_resource::<T>(&provider);
+		Resources::<T>::mutate(index, |resource| {
+			if let Some(resource) = resource {
+				resource.specification = Specification::HighRanking;
+			}
+		});
+		set_thresholds::<T>();
+		let now = T::AttestationInterval::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+		ResourceAttestations::<T>::insert(
+			index,
+			HardwareAttestation { scores: scores(), block_number: Zero::zero(), timestamp: 0 },
+		);
+		FlaggedResources::<T>::insert(index, T::BlockNumber::zero());
+
+		let attestation =
+			AttestationPayload { block_number: now, resource_index: index, scores: scores(), public };
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&attestation)
+			.ok_or("couldn't make signature")?;
+		let call = Call::<T>::attest_hardware { attestation, signature };
+		let call_enc = call.encode();
+	}: {
+		Provider::<T>::validate_unsigned(TransactionSource::InBlock, &call)
+			.map_err(<&str>::from)?;
+		<Call<T> as Decode>::decode(&mut &*call_enc)
+			.expect("call is encoded above, encoding must be correct")
+			.dispatch_bypass_filter(RawOrigin::None.into())?;
+	}
+	verify {
+		assert_eq!(Provider::<T>::flagged_since(index), None);
+		assert_eq!(
+			Provider::<T>::resource(index).map(|resource| resource.specification),
+			Some(Specification::General)
+		);
+		assert_last_event::<T>(Event::HardwareAttested { index, scores: scores() }.into());
+	}
+
+	validate_unsigned_and_then_answer_challenge {
+		let key =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+				Some(b"0x0505050505050505050505050505050505050505050505050505050505050505".to_vec()),
+			);
+		let public: T::Public =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into();
+		let provider = public.clone().into_account();
+
+		let index = create_resource::<T>(&provider);
+		let nonce = T::Hash::default();
+		let deadline = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::ChallengeDeadline::get());
+		AuditChallenges::<T>::insert(index, AuditChallenge { nonce, deadline });
+
+		let peer_id = Provider::<T>::resource(index).expect("the resource exists").peer_id.into_inner();
+		let response = ChallengeResponsePayload { resource_index: index, nonce, peer_id, public };
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&response)
+			.ok_or("couldn't make signature")?;
+		let call = Call::<T>::answer_challenge { response, signature };
+		let call_enc = call.encode();
+	}: {
+		Provider::<T>::validate_unsigned(TransactionSource::InBlock, &call)
+			.map_err(<&str>::from)?;
+		<Call<T> as Decode>::decode(&mut &*call_enc)
+			.expect("call is encoded above, encoding must be correct")
+			.dispatch_bypass_filter(RawOrigin::None.into())?;
+	}
+	verify {
+		assert_eq!(Provider::<T>::audit_challenge(index), None);
+		assert_last_event::<T>(Event::ChallengeAnswered { index }.into());
+	}
+
+	process_audit {
+		// as many resources as an audit samples, every probe reads one of them
+		for i in 0 .. T::AuditSampleSize::get() {
+			create_resource::<T>(&account("provider", i, SEED));
+		}
+		let now = T::AuditPeriod::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+		let deadline = now.saturating_add(T::ChallengeDeadline::get());
+	}: {
+		Provider::<T>::process_audit(now);
+	}
+	verify {
+		assert!(!Provider::<T>::challenge_deadlines(deadline).is_empty());
+	}
+
+	process_challenge_deadlines {
+		let c in 0 .. T::AuditSampleSize::get();
+
+		let deadline = T::ChallengeDeadline::get();
+		let mut challenged = BoundedVec::<u64, T::AuditSampleSize>::default();
+		for i in 0 .. c {
+			let index = create_resource::<T>(&account("provider", i, SEED));
+			AuditChallenges::<T>::insert(
+				index,
+				AuditChallenge { nonce: T::Hash::default(), deadline },
+			);
+			challenged.try_push(index).expect("at most as many resources as the bound; qed");
+		}
+		ChallengeDeadlines::<T>::insert(deadline, challenged);
+		frame_system::Pallet::<T>::set_block_number(deadline);
+	}: {
+		Provider::<T>::process_challenge_deadlines(deadline);
+	}
+	verify {
+		assert_eq!(AuditChallenges::<T>::iter().count(), 0);
+		assert!(Resources::<T>::iter_values().all(|resource| resource.rental_statistics.fault_count == 1));
+	}
+
+	process_expiries {
+		let e in 0 .. T::MaxExpiriesPerBlock::get();
+
+		let duration: T::BlockNumber = RENT_DURATION.into();
+		for i in 0 .. e {
+			let index = create_resource::<T>(&account("provider", i, SEED));
+			rent::<T>(&funded_account::<T>("renter", i), index, duration);
+		}
+		let end_of_rent = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+		frame_system::Pallet::<T>::set_block_number(end_of_rent);
+	}: {
+		Provider::<T>::process_expiries(end_of_rent);
+	}
+	verify {
+		assert_eq!(RentalOrders::<T>::iter().count(), 0);
+	}
+
+	process_heartbeat_deadlines {
+		let h in 0 .. T::MaxHeartbeatChecksPerBlock::get();
+
+		// every resource is bonded and leased in slices to the most renters, so its slash is
+		// shared by all of them when going offline
+		let timeout = Provider::<T>::heartbeat_timeout();
+		for i in 0 .. h {
+			let provider = funded_account::<T>("provider", i);
+			let index = create_resource::<T>(&provider);
+			Provider::<T>::bond_resource(RawOrigin::Signed(provider).into(), index)?;
+			rent_slices::<T>(index, max_leases::<T>(), timeout.saturating_add(RENT_DURATION.into()));
+		}
+		let deadline = frame_system::Pallet::<T>::block_number().saturating_add(timeout);
+		frame_system::Pallet::<T>::set_block_number(deadline);
+		HeartbeatCursor::<T>::put(deadline);
+	}: {
+		Provider::<T>::process_heartbeat_deadlines(deadline);
+	}
+	verify {
+		assert!(Resources::<T>::iter_values().all(|resource| resource.status == ResourceStatus::Offline));
+	}
+
+	process_price_adjustment {
+		let c in 0 .. T::MaxPriceAdjustmentsPerBlock::get();
+
+		// half of the resources of every class are leased
+		for i in 0 .. c {
+			let class = ResourceClass {
+				specification: Specification::General,
+				cpu: 1 << (i % 64),
+				memory: 1 << (i / 64),
+			};
+			ClassMarkets::<T>::insert(
+				class,
+				ClassMarket { resources: 2, leased: 1_000_000_000, multiplier: FixedU128::one() },
+			);
+		}
+		let now = T::PriceAdjustmentPeriod::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Provider::<T>::process_price_adjustment(now);
+	}
+	verify {
+		assert_eq!(ClassMarkets::<T>::iter().count(), c as usize);
+	}
+
+	process_era {
+		// the era that can no longer be claimed is forgotten
+		let depth = T::RewardHistoryDepth::get();
+		CurrentEra::<T>::put(depth);
+		let provider: T::AccountId = account("provider", 0, SEED);
+		Provider::<T>::add_provider_points(&provider, POINTS, 0);
+		ErasTotalPoints::<T>::insert(0, POINTS as u128);
+		ErasRewardPot::<T>::insert(0, T::EraRewardPot::get());
+		let now = T::EraDuration::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Provider::<T>::process_era(now);
+	}
+	verify {
+		assert_eq!(Provider::<T>::current_era(), depth + 1);
+	}
+
+	set_specification_threshold {
+		let origin = T::ThresholdOrigin::successful_origin();
+		let threshold = SpecificationThreshold {
+			cpu: CPU,
+			memory: MEMORY,
+			cpu_hashrate_score: SCORE,
+			memory_memcpy_score: SCORE,
+			disk_sequential_write_score: SCORE,
+			disk_random_write_score: SCORE,
+		};
+		let call = Call::<T>::set_specification_threshold {
+			specification: Specification::HighRanking,
+			threshold: Some(threshold.clone()),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Provider::<T>::specification_threshold(Specification::HighRanking), Some(threshold));
+	}
+
+	refresh_specification {
+		// a resource registered before the thresholds were raised drops to `General`
+		let index = create_resource::<T>(&account("provider", 0, SEED));
+		Resources::<T>::mutate(index, |resource| {
+			if let Some(resource) = resource {
+				resource.specification = Specification::HighRanking;
+			}
+		});
+		set_thresholds::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert_last_event::<T>(
+			Event::ResourceSpecificationChanged { index, specification: Specification::General }
+				.into(),
+		);
+	}
+
+	impl_benchmark_test_suite!(Provider, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,52 +1,468 @@
+#![recursion_limit = "256"]
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
 use frame_support::{
-	dispatch::DispatchResult, pallet_prelude::*, sp_runtime::traits::Convert, traits::Currency,
+	dispatch::{DispatchResult, Dispatchable},
+	pallet_prelude::*,
+	sp_runtime::{
+		helpers_128bit::multiply_by_rational,
+		offchain::storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
+		traits::{
+			AccountIdConversion, Convert, Hash, IdentifyAccount, One, Saturating,
+			TrailingZeroInput, Zero,
+		},
+		FixedPointNumber, FixedU128, PerThing, Perbill, RuntimeAppPublic, SaturatedConversion,
+	},
+	storage::{with_transaction, TransactionOutcome},
+	traits::{
+		schedule::{self, DispatchTime, Named as ScheduleNamed},
+		tokens::fungibles::{self, Inspect as _, Transfer},
+		BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+		StorageVersion, UnixTime,
+	},
+	transactional, PalletId,
+};
+use frame_system::{
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+		SigningTypes,
+	},
+	pallet_prelude::*,
+};
+use sp_core::crypto::KeyTypeId;
+use sp_hamster::{
+	p_provider::{
+		Auction, AuctionKind, AuditChallenge, ClassMarket, ComputingResource, DiskKind,
+		HardwareAttestation, HardwareScores, MatchPolicy, PriceBounds, ProviderInterface,
+		ProviderPoints, RentalOrder, Reputation, Reservation, ResourceClass, ResourceConfig,
+		ResourceDemand, ResourceRentalInfo, ResourceRentalStatistics, ResourceSlice,
+		ResourceStatus, Specification, SpecificationThreshold, HWBENCH_STORAGE_KEY, MAX_RATING,
+		PEER_ID_STORAGE_KEY,
+	},
+	EraIndex,
 };
-use frame_system::pallet_prelude::*;
 use sp_std::{convert::TryInto, vec::Vec};
 
 pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub(crate) const LOG_TARGET: &str = "runtime::provider";
+
+/// key type of the keys used by provider nodes to sign heartbeats
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"prov");
+
+/// prefix of the offchain storage keys locking heartbeat submission
+const HEARTBEAT_LOCK_PREFIX: &[u8] = b"provider::heartbeat::";
+
+/// prefix of the offchain storage keys locking hardware attestation submission
+const ATTESTATION_LOCK_PREFIX: &[u8] = b"provider::attestation::";
+
+/// prefix of the offchain storage keys locking audit challenge response submission
+const CHALLENGE_LOCK_PREFIX: &[u8] = b"provider::challenge::";
+
+/// subject of the randomness sampling the audited resources
+const AUDIT_RANDOMNESS_SUBJECT: &[u8] = b"provider::audit";
+
+/// number of random resource indexes looked at per resource an audit challenges, some of them
+/// being removed, offline or already challenged
+const AUDIT_PROBES_PER_SAMPLE: u32 = 4;
+
+/// prefix of the names of the scheduled tasks activating reservations
+const RESERVATION_TASK_ID: [u8; 8] = *b"provresv";
+
+/// prefix of the names of the scheduled tasks closing auctions
+const AUCTION_TASK_ID: [u8; 8] = *b"provauct";
+
+/// priority of the scheduled tasks, a hard deadline so they are never postponed to a later block
+const TASK_PRIORITY: schedule::Priority = schedule::HARD_DEADLINE;
+
+/// number of blocks looked at to find room in a full expiry queue
+const MAX_QUEUE_PROBES: u32 = 16;
+
+/// current storage version, see [`migrations`] for the upgrades to it
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// provider heartbeat, hardware attestation and audit challenge response signing key
+	pub struct ProviderAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for ProviderAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// heartbeat sent by a provider node for one of its resources
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct HeartbeatPayload<Public, BlockNumber> {
+	/// block at which the heartbeat was sent
+	pub block_number: BlockNumber,
+	/// resource index
+	pub resource_index: u64,
+	/// public key of the provider
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for HeartbeatPayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// hardware benchmark scores measured by a provider node for one of its resources
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AttestationPayload<Public, BlockNumber> {
+	/// block at which the scores were signed
+	pub block_number: BlockNumber,
+	/// resource index
+	pub resource_index: u64,
+	/// scores of the last hardware benchmark of the node
+	pub scores: HardwareScores,
+	/// public key of the provider
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for AttestationPayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// response of a provider node to the audit challenge of one of its resources
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ChallengeResponsePayload<Public, Hash> {
+	/// resource index
+	pub resource_index: u64,
+	/// nonce of the challenge
+	pub nonce: Hash,
+	/// peer id of the answering node, the one the resource is registered with
+	pub peer_id: Vec<u8>,
+	/// public key of the provider
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for ChallengeResponsePayload<T::Public, T::Hash> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
 
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+type AssetBalanceOf<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// assets a resource can be paid in, with its unit price in each of them
+type AssetPricesOf<T> =
+	BoundedVec<(<T as Config>::AssetId, u128), <T as Config>::MaxAcceptedAssets>;
+
+type ResourceOf<T> = ComputingResource<
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::StringLimit,
+>;
+
+type ResourceConfigOf<T> = ResourceConfig<<T as Config>::StringLimit>;
+
+type ResourceDemandOf<T> = ResourceDemand<<T as Config>::StringLimit>;
+
+type ReservationOf<T> =
+	Reservation<<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::AccountId>;
+
+type AuctionOf<T> =
+	Auction<<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::AccountId>;
+
+type CallOf<T> = <T as Config>::Call;
+
+/// byte string describing a resource
+type BoundedStringOf<T> = BoundedVec<u8, <T as Config>::StringLimit>;
+
+/// points checkpoints of a provider, see [`PointsHistory`]
+type PointsHistoryOf<T> = BoundedVec<(EraIndex, u128), HistoryLength<T>>;
+
+/// blocks booked by the reservations of a resource, see [`ResourceReservations`]
+type ReservationWindowsOf<T> = BoundedVec<
+	(<T as frame_system::Config>::BlockNumber, <T as frame_system::Config>::BlockNumber),
+	<T as Config>::MaxReservationsPerResource,
+>;
+
+/// unbonding chunks of a provider, see [`Unbonding`]
+type UnbondingChunksOf<T> = BoundedVec<
+	(<T as frame_system::Config>::BlockNumber, BalanceOf<T>),
+	<T as Config>::MaxUnbondingChunks,
+>;
+
+/// number of points checkpoints kept for a provider, one per claimable era and the one before
+/// them
+pub struct HistoryLength<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> Get<u32> for HistoryLength<T> {
+	fn get() -> u32 {
+		T::RewardHistoryDepth::get().saturating_add(1)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use sp_hamster::{p_provider, p_provider::ComputingResource, Balance};
+
+	/// creates and funds the assets the benchmarked rentals are paid in
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<AssetId, AccountId> {
+		/// the `i`-th asset
+		fn asset(i: u32) -> AssetId;
+		/// create `asset` if it does not exist and give `who` enough of it to rent resources
+		fn fund(asset: AssetId, who: &AccountId);
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// currency to pay fees and hold balances
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// amount converted to numbers
 		type BalanceToNumber: Convert<BalanceOf<Self>, u128>;
 
 		type NumberToBalance: Convert<u128, BalanceOf<Self>>;
+
+		/// identifier of the assets rentals can be paid in
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+
+		/// assets rentals can be paid in besides `Currency`
+		type Assets: fungibles::Transfer<Self::AccountId, AssetId = Self::AssetId>;
+
+		/// pallet id, its account escrows the deposits of the rentals paid in assets
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// maximum number of rental orders expiring in a single block, further orders are queued
+		/// in the following blocks
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+
+		/// the identifier type for the keys signing heartbeats and hardware attestations
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// number of blocks between two heartbeats of a resource
+		#[pallet::constant]
+		type HeartbeatInterval: Get<Self::BlockNumber>;
+
+		/// number of heartbeat intervals a resource can miss before it is marked offline
+		#[pallet::constant]
+		type MaxMissedHeartbeats: Get<u32>;
+
+		/// maximum number of heartbeat deadlines checked in a single block, the remaining ones are
+		/// checked in the following blocks
+		#[pallet::constant]
+		type MaxHeartbeatChecksPerBlock: Get<u32>;
+
+		/// priority of unsigned heartbeat and hardware attestation transactions
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// number of blocks between two hardware attestations of a resource
+		#[pallet::constant]
+		type AttestationInterval: Get<Self::BlockNumber>;
+
+		/// time source of the hardware attestations
+		type UnixTime: UnixTime;
+
+		/// source of the randomness sampling the audited resources
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// the overarching call type, reservations are activated and auctions closed by
+		/// scheduling calls of this pallet
+		type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
+
+		/// scheduler dispatching the activations of reservations at their first block and the
+		/// closing of auctions
+		type Scheduler: ScheduleNamed<Self::BlockNumber, CallOf<Self>, Self::PalletsOrigin>;
+
+		/// overarching type of all pallets origins
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// number of blocks between two audits, each challenging a random sample of the
+		/// `Unused` and `Inuse` resources, zero disables audits
+		#[pallet::constant]
+		type AuditPeriod: Get<Self::BlockNumber>;
+
+		/// maximum number of resources challenged by an audit
+		#[pallet::constant]
+		type AuditSampleSize: Get<u32>;
+
+		/// number of blocks a challenged resource has to answer
+		#[pallet::constant]
+		type ChallengeDeadline: Get<Self::BlockNumber>;
+
+		/// share of the reputation counters of a resource kept from an era to the next
+		#[pallet::constant]
+		type ReputationDecay: Get<Perbill>;
+
+		/// number of faulty blocks every fault of a resource adds to its reputation, on top of
+		/// its duration
+		#[pallet::constant]
+		type ReputationFaultPenalty: Get<u32>;
+
+		/// number of blocks between two adjustments of the price multipliers of the resource
+		/// classes, zero disables them
+		#[pallet::constant]
+		type PriceAdjustmentPeriod: Get<Self::BlockNumber>;
+
+		/// maximum number of price multipliers adjusted in a single block, the remaining ones are
+		/// adjusted in the following blocks
+		#[pallet::constant]
+		type MaxPriceAdjustmentsPerBlock: Get<u32>;
+
+		/// allocated share of the resources of a class the price multipliers steer towards
+		#[pallet::constant]
+		type TargetUtilization: Get<Perbill>;
+
+		/// how fast the price multipliers move when the utilization is off target
+		#[pallet::constant]
+		type PriceAdjustmentVariability: Get<FixedU128>;
+
+		/// lowest price multiplier of a resource class
+		#[pallet::constant]
+		type MinimumPriceMultiplier: Get<FixedU128>;
+
+		/// highest price multiplier of a resource class
+		#[pallet::constant]
+		type MaximumPriceMultiplier: Get<FixedU128>;
+
+		/// bond required per cpu of a resource
+		#[pallet::constant]
+		type BondPerCpu: Get<BalanceOf<Self>>;
+
+		/// bond required per unit of memory of a resource
+		#[pallet::constant]
+		type BondPerMemory: Get<BalanceOf<Self>>;
+
+		/// bond required per unit of storage capacity of a resource
+		#[pallet::constant]
+		type BondPerStorage: Get<BalanceOf<Self>>;
+
+		/// bond required per Mbps of network bandwidth of a resource
+		#[pallet::constant]
+		type BondPerBandwidth: Get<BalanceOf<Self>>;
+
+		/// bond required per accelerator of a resource
+		#[pallet::constant]
+		type BondPerAccelerator: Get<BalanceOf<Self>>;
+
+		/// part of the bond slashed and paid to the renter when a rented resource goes offline, or
+		/// of the deposit of a lease paid in an asset taken from the provider and refunded to the
+		/// renter
+		#[pallet::constant]
+		type OfflineSlashRatio: Get<Perbill>;
+
+		/// part of the rental price reserved when booking a resource, forfeited to the provider
+		/// if the renter can not pay for the lease when it starts
+		#[pallet::constant]
+		type ReservationDeposit: Get<Perbill>;
+
+		/// number of blocks an unbonded amount stays reserved before it can be withdrawn
+		#[pallet::constant]
+		type UnbondingPeriod: Get<Self::BlockNumber>;
+
+		/// number of blocks in a provider reward era
+		#[pallet::constant]
+		type EraDuration: Get<Self::BlockNumber>;
+
+		/// reward shared among providers at the end of each era
+		#[pallet::constant]
+		type EraRewardPot: Get<BalanceOf<Self>>;
+
+		/// account the era rewards are paid from, rewards are minted if `None`
+		type RewardPotAccount: Get<Option<Self::AccountId>>;
+
+		/// number of ended eras whose rewards can still be claimed
+		#[pallet::constant]
+		type RewardHistoryDepth: Get<EraIndex>;
+
+		/// maximum number of resources matching a demand the best one is chosen among
+		#[pallet::constant]
+		type MaxMatchCandidates: Get<u32>;
+
+		/// maximum length of the peer id, ip, system and cpu model of a resource
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// maximum number of resources registered by a provider
+		#[pallet::constant]
+		type MaxResourcesPerProvider: Get<u32>;
+
+		/// maximum number of active rental orders of a renter
+		#[pallet::constant]
+		type MaxOrdersPerRenter: Get<u32>;
+
+		/// maximum number of concurrent leases of a divisible resource
+		#[pallet::constant]
+		type MaxLeasesPerResource: Get<u32>;
+
+		/// maximum number of pending reservations of a resource
+		#[pallet::constant]
+		type MaxReservationsPerResource: Get<u32>;
+
+		/// maximum number of sealed bids committed in an auction
+		#[pallet::constant]
+		type MaxBidsPerAuction: Get<u32>;
+
+		/// maximum number of assets a resource can be paid in
+		#[pallet::constant]
+		type MaxAcceptedAssets: Get<u32>;
+
+		/// maximum number of unbonding chunks of a provider
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+
+		/// origin allowed to set the thresholds of the specifications
+		type ThresholdOrigin: EnsureOrigin<Self::Origin>;
+
+		/// weight information for the extrinsics and hooks of this pallet
+		type WeightInfo: WeightInfo;
+
+		/// creates the assets of the benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId, Self::AccountId>;
 	}
 
 	#[pallet::pallet]
-	#[pallet::generate_store(pub (super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// resource information
 	#[pallet::storage]
 	#[pallet::getter(fn resource)]
-	pub(super) type Resources<T: Config> = StorageMap<
-		_,
-		Twox64Concat,
-		u64,
-		ComputingResource<T::BlockNumber, T::AccountId>,
-		OptionQuery,
-	>;
+	pub(super) type Resources<T: Config> =
+		StorageMap<_, Twox64Concat, u64, ResourceOf<T>, OptionQuery>;
+
+	/// unused resources by specification, the ones a demand is matched against
+	#[pallet::storage]
+	pub(super) type UnusedResources<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, Specification, Twox64Concat, u64, (), OptionQuery>;
 
 	/// resource index
 	#[pallet::storage]
@@ -56,21 +472,298 @@ pub mod pallet {
 	/// resource provider and resource association
 	#[pallet::storage]
 	#[pallet::getter(fn provider)]
-	pub(super) type Providers<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, Vec<u64>, OptionQuery>;
+	pub(super) type Providers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<u64, T::MaxResourcesPerProvider>,
+		OptionQuery,
+	>;
+
+	/// peer id and resource association
+	#[pallet::storage]
+	#[pallet::getter(fn peer_id_resource)]
+	pub(super) type PeerIds<T: Config> =
+		StorageMap<_, Twox64Concat, BoundedStringOf<T>, u64, OptionQuery>;
+
+	/// rental order information
+	#[pallet::storage]
+	#[pallet::getter(fn rental_order)]
+	pub(super) type RentalOrders<T: Config> =
+		StorageMap<_, Twox64Concat, u64, RentalOrder<T::BlockNumber, T::AccountId>, OptionQuery>;
+
+	/// rental order index
+	#[pallet::storage]
+	#[pallet::getter(fn order_index)]
+	pub(super) type OrderIndex<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// renter and rental order association
+	#[pallet::storage]
+	#[pallet::getter(fn renter_orders)]
+	pub(super) type RenterOrders<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<u64, T::MaxOrdersPerRenter>,
+		OptionQuery,
+	>;
+
+	/// active rental orders of a resource, a divisible resource has one per leased slice
+	#[pallet::storage]
+	#[pallet::getter(fn resource_leases)]
+	pub(super) type ResourceLeases<T: Config> =
+		StorageMap<_, Twox64Concat, u64, BoundedVec<u64, T::MaxLeasesPerResource>, OptionQuery>;
+
+	/// rental orders to settle, keyed by the block their lease ends
+	#[pallet::storage]
+	#[pallet::getter(fn order_expiries)]
+	pub(super) type OrderExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<u64, T::MaxExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// pending reservation information
+	#[pallet::storage]
+	#[pallet::getter(fn reservation)]
+	pub(super) type Reservations<T: Config> =
+		StorageMap<_, Twox64Concat, u64, ReservationOf<T>, OptionQuery>;
+
+	/// reservation index
+	#[pallet::storage]
+	#[pallet::getter(fn reservation_index)]
+	pub(super) type ReservationIndex<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// blocks `[start, end)` booked by the pending reservations of a resource
+	#[pallet::storage]
+	#[pallet::getter(fn resource_reservations)]
+	pub(super) type ResourceReservations<T: Config> =
+		StorageMap<_, Twox64Concat, u64, ReservationWindowsOf<T>, OptionQuery>;
+
+	/// auction information
+	#[pallet::storage]
+	#[pallet::getter(fn auction)]
+	pub(super) type Auctions<T: Config> =
+		StorageMap<_, Twox64Concat, u64, AuctionOf<T>, OptionQuery>;
+
+	/// auction index
+	#[pallet::storage]
+	#[pallet::getter(fn auction_index)]
+	pub(super) type AuctionIndex<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// auction a resource is listed in
+	#[pallet::storage]
+	#[pallet::getter(fn resource_auction)]
+	pub(super) type ResourceAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, u64, u64, OptionQuery>;
+
+	/// bids of an auction holding funds, as `(commitment, reserved amount)`, the commitment of
+	/// a sealed bid is cleared once revealed
+	#[pallet::storage]
+	#[pallet::getter(fn auction_bid)]
+	pub(super) type AuctionBids<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u64,
+		Blake2_128Concat,
+		T::AccountId,
+		(Option<T::Hash>, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// utilization and price multiplier of the resource classes with registered resources
+	#[pallet::storage]
+	#[pallet::getter(fn class_market)]
+	pub(super) type ClassMarkets<T: Config> =
+		StorageMap<_, Blake2_128Concat, ResourceClass, ClassMarket, OptionQuery>;
+
+	/// last resource class whose price multiplier was adjusted, while an adjustment started at
+	/// the beginning of a `PriceAdjustmentPeriod` goes on
+	#[pallet::storage]
+	pub(super) type PriceAdjustmentCursor<T: Config> = StorageValue<_, ResourceClass, OptionQuery>;
+
+	/// unit price bounds of the resources whose price follows the multiplier of their class
+	#[pallet::storage]
+	#[pallet::getter(fn price_bounds)]
+	pub(super) type DynamicPrices<T: Config> =
+		StorageMap<_, Twox64Concat, u64, PriceBounds, OptionQuery>;
+
+	/// assets a resource can be paid in, with its unit price in each of them
+	#[pallet::storage]
+	#[pallet::getter(fn asset_prices)]
+	pub(super) type AssetPrices<T: Config> =
+		StorageMap<_, Twox64Concat, u64, AssetPricesOf<T>, ValueQuery>;
+
+	/// asset an active rental order is paid in, orders without one are paid in `Currency`
+	#[pallet::storage]
+	#[pallet::getter(fn order_asset)]
+	pub(super) type OrderAssets<T: Config> =
+		StorageMap<_, Twox64Concat, u64, T::AssetId, OptionQuery>;
+
+	/// assets paid out of the escrow account that could not be transferred to their owner, they
+	/// stay in the escrow account until claimed
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_assets)]
+	pub(super) type UnclaimedAssets<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AssetId, u128, ValueQuery>;
+
+	/// last heartbeat of a resource, or the block up to which its downtime has been accounted
+	#[pallet::storage]
+	#[pallet::getter(fn resource_heartbeat)]
+	pub(super) type ResourceHeartbeats<T: Config> =
+		StorageMap<_, Twox64Concat, u64, T::BlockNumber, OptionQuery>;
+
+	/// resources to check for missed heartbeats, keyed by the block their heartbeat deadline
+	/// passes and the resource index
+	#[pallet::storage]
+	pub(super) type HeartbeatDeadlines<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, u64, (), OptionQuery>;
+
+	/// first block whose heartbeat deadlines have not all been checked yet
+	#[pallet::storage]
+	#[pallet::getter(fn heartbeat_cursor)]
+	pub(super) type HeartbeatCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// collateral bonded for a resource
+	#[pallet::storage]
+	#[pallet::getter(fn resource_bond)]
+	pub(super) type ResourceBonds<T: Config> =
+		StorageMap<_, Twox64Concat, u64, BalanceOf<T>, OptionQuery>;
+
+	/// unbonded collateral of a provider waiting for the end of the unbonding period, as
+	/// `(unlock block, amount)`
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding)]
+	pub(super) type Unbonding<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, UnbondingChunksOf<T>, OptionQuery>;
+
+	/// points of a provider
+	#[pallet::storage]
+	#[pallet::getter(fn provider_points)]
+	pub(super) type Points<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, ProviderPoints, OptionQuery>;
+
+	/// sum of the resource points of all providers
+	#[pallet::storage]
+	#[pallet::getter(fn total_resource_points)]
+	pub(super) type TotalResourcePoints<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// sum of the duration points of all providers
+	#[pallet::storage]
+	#[pallet::getter(fn total_duration_points)]
+	pub(super) type TotalDurationPoints<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// current provider reward era
+	#[pallet::storage]
+	#[pallet::getter(fn current_era)]
+	pub(super) type CurrentEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+	/// reward pot of an ended era
+	#[pallet::storage]
+	#[pallet::getter(fn eras_reward_pot)]
+	pub(super) type ErasRewardPot<T: Config> =
+		StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, OptionQuery>;
+
+	/// total points of all providers at the end of an era
+	#[pallet::storage]
+	#[pallet::getter(fn eras_total_points)]
+	pub(super) type ErasTotalPoints<T: Config> =
+		StorageMap<_, Twox64Concat, EraIndex, u128, ValueQuery>;
+
+	/// points of a provider at the end of the eras they changed in, as `(era, points)` by
+	/// increasing era, the points of an era being those of the last checkpoint up to it
+	#[pallet::storage]
+	#[pallet::getter(fn points_history)]
+	pub(super) type PointsHistory<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, PointsHistoryOf<T>, ValueQuery>;
+
+	/// claimable eras whose reward has been paid to a provider
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_eras)]
+	pub(super) type ClaimedEras<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<EraIndex, T::RewardHistoryDepth>,
+		ValueQuery,
+	>;
+
+	/// hardware benchmark scores of a resource, a resource without scores scores zero
+	#[pallet::storage]
+	#[pallet::getter(fn resource_scores)]
+	pub(super) type ResourceScores<T: Config> =
+		StorageMap<_, Twox64Concat, u64, HardwareScores, OptionQuery>;
+
+	/// minimum capacity and scores of the resources of a specification
+	///
+	/// a specification above `General` without a threshold can not be reached, `General` without
+	/// one has no minimum
+	#[pallet::storage]
+	#[pallet::getter(fn specification_threshold)]
+	pub(super) type SpecificationThresholds<T: Config> =
+		StorageMap<_, Twox64Concat, Specification, SpecificationThreshold, OptionQuery>;
+
+	/// last hardware attestation of a resource
+	#[pallet::storage]
+	#[pallet::getter(fn resource_attestation)]
+	pub(super) type ResourceAttestations<T: Config> =
+		StorageMap<_, Twox64Concat, u64, HardwareAttestation<T::BlockNumber>, OptionQuery>;
+
+	/// resources whose declared or attested hardware does not reach the threshold of `General`,
+	/// with the block they were flagged at, they can not be rented until their hardware reaches it
+	#[pallet::storage]
+	#[pallet::getter(fn flagged_since)]
+	pub(super) type FlaggedResources<T: Config> =
+		StorageMap<_, Twox64Concat, u64, T::BlockNumber, OptionQuery>;
+
+	/// pending audit challenge of a resource
+	#[pallet::storage]
+	#[pallet::getter(fn audit_challenge)]
+	pub(super) type AuditChallenges<T: Config> =
+		StorageMap<_, Twox64Concat, u64, AuditChallenge<T::BlockNumber, T::Hash>, OptionQuery>;
+
+	/// resources challenged by an audit, keyed by the deadline of their challenges
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_deadlines)]
+	pub(super) type ChallengeDeadlines<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<u64, T::AuditSampleSize>,
+		ValueQuery,
+	>;
+
+	/// reputation of a resource, last stored when one of its leases ended, a renter rated it or
+	/// it was refreshed
+	#[pallet::storage]
+	#[pallet::getter(fn resource_reputation)]
+	pub(super) type ResourceReputations<T: Config> =
+		StorageMap<_, Twox64Concat, u64, Reputation, OptionQuery>;
+
+	/// rating given by the renter of an active order
+	#[pallet::storage]
+	#[pallet::getter(fn order_rating)]
+	pub(super) type OrderRatings<T: Config> = StorageMap<_, Twox64Concat, u64, u8, OptionQuery>;
 
 	// The genesis config type.
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
-		pub resource: Vec<(u64, ComputingResource<T::BlockNumber, T::AccountId>)>,
+		pub resource: Vec<(u64, ResourceOf<T>)>,
 		pub resource_index: u64,
+		pub specification_thresholds: Vec<(Specification, SpecificationThreshold)>,
 	}
 
 	// The default value for the genesis config type.
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { resource: Default::default(), resource_index: Default::default() }
+			Self {
+				resource: Default::default(),
+				resource_index: Default::default(),
+				specification_thresholds: Default::default(),
+			}
 		}
 	}
 
@@ -78,29 +771,3214 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			<ResourceIndex<T>>::put(&self.resource_index);
+			<ResourceIndex<T>>::put(self.resource_index);
+			for (specification, threshold) in &self.specification_thresholds {
+				<SpecificationThresholds<T>>::insert(specification, threshold);
+			}
 			for (a, b) in &self.resource {
-				<Resources<T>>::insert(a, b);
+				Pallet::<T>::insert_resource(*a, b, Zero::zero()).expect(
+					"genesis resources have unique peer ids within the bound of a provider",
+				);
 			}
 		}
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (super) fn deposit_event)]
-	pub enum Event<T: Config> {}
+	pub enum Event<T: Config> {
+		/// a resource has been registered
+		ResourceRegistered { who: T::AccountId, index: u64, peer_id: BoundedStringOf<T> },
+		/// a resource has been updated
+		ResourceUpdated { who: T::AccountId, index: u64 },
+		/// the rentable duration of a resource has been extended
+		ResourceDurationAdded { who: T::AccountId, index: u64, duration: T::BlockNumber },
+		/// a resource has been removed
+		ResourceRemoved { who: T::AccountId, index: u64 },
+		/// a provider opted a resource in dynamic pricing between the given bounds, or out of it
+		/// if `None`
+		DynamicPricingSet { index: u64, bounds: Option<PriceBounds> },
+		/// a provider set the assets a resource can be paid in, with its unit price in each of
+		/// them
+		AssetPricesSet { index: u64, prices: Vec<(T::AssetId, AssetBalanceOf<T>)> },
+		/// a provider allowed or stopped leasing slices of a resource
+		ResourceDivisibilitySet { index: u64, divisible: bool },
+		/// a resource has been rented, paying in `asset_id` or in `Currency` if `None`
+		///
+		/// the amounts of the events of an order paid in an asset are in units of that asset
+		ResourceRented {
+			renter: T::AccountId,
+			resource_index: u64,
+			order_index: u64,
+			slice: ResourceSlice,
+			end_of_rent: T::BlockNumber,
+			deposit: BalanceOf<T>,
+			asset_id: Option<T::AssetId>,
+		},
+		/// a rental order has been settled and paid to the provider
+		OrderSettled { order_index: u64, provider: T::AccountId, amount: BalanceOf<T> },
+		/// a rental order has been extended, `deposit` is the additional amount reserved
+		OrderRenewed { order_index: u64, end_of_rent: T::BlockNumber, deposit: BalanceOf<T> },
+		/// a heartbeat has been received for a resource
+		HeartbeatReceived { index: u64 },
+		/// a resource missed too many heartbeats and has been marked offline
+		ResourceOffline { index: u64 },
+		/// an offline resource sent a heartbeat again
+		ResourceOnline { index: u64, status: ResourceStatus },
+		/// collateral has been bonded for a resource, `amount` is the total bond
+		ResourceBonded { index: u64, amount: BalanceOf<T> },
+		/// the bond of a resource has been unbonded and can be withdrawn at `unlock_at`
+		ResourceUnbonded { index: u64, amount: BalanceOf<T>, unlock_at: T::BlockNumber },
+		/// unbonded collateral has been withdrawn
+		Withdrawn { who: T::AccountId, amount: BalanceOf<T> },
+		/// part of the bond of a resource has been slashed and paid to the renter
+		BondSlashed { index: u64, renter: T::AccountId, amount: BalanceOf<T> },
+		/// part of the deposit of a lease paid in an asset has been taken from the provider and
+		/// refunded to the renter, the resource having gone offline
+		AssetSlashed {
+			index: u64,
+			renter: T::AccountId,
+			asset_id: T::AssetId,
+			amount: AssetBalanceOf<T>,
+		},
+		/// a payment in an asset could not be transferred to `who` and can be claimed
+		AssetsUnclaimed { who: T::AccountId, asset_id: T::AssetId, amount: AssetBalanceOf<T> },
+		/// unclaimed assets have been transferred to their owner
+		AssetsClaimed { who: T::AccountId, asset_id: T::AssetId, amount: AssetBalanceOf<T> },
+		/// a reward era has ended and its points have been recorded
+		EraEnded { era: EraIndex, reward_pot: BalanceOf<T>, total_points: u128 },
+		/// a provider has been paid its share of an era reward
+		ProviderRewarded { era: EraIndex, provider: T::AccountId, amount: BalanceOf<T> },
+		/// a rental order has been ended early by the renter
+		OrderTerminated {
+			order_index: u64,
+			renter: T::AccountId,
+			paid: BalanceOf<T>,
+			refund: BalanceOf<T>,
+		},
+		/// a resource has been reserved for the blocks `[start, end)`
+		ResourceReserved {
+			renter: T::AccountId,
+			resource_index: u64,
+			reservation_index: u64,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+			deposit: BalanceOf<T>,
+		},
+		/// a reservation has been cancelled by the renter before it started
+		ReservationCancelled { reservation_index: u64, renter: T::AccountId },
+		/// the lease of a reservation has started as the rental order `order_index`
+		ReservationActivated { reservation_index: u64, order_index: u64 },
+		/// the lease of a reservation could not start for another reason than the balance of the
+		/// renter, the deposit has been refunded to the renter
+		ReservationFailed { reservation_index: u64, renter: T::AccountId },
+		/// the renter could not pay for the lease when the reservation started, the deposit has
+		/// been paid to the provider
+		ReservationForfeited {
+			reservation_index: u64,
+			renter: T::AccountId,
+			provider: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// a resource has been listed in an auction closing at `close_at`
+		AuctionListed {
+			resource_index: u64,
+			auction_index: u64,
+			kind: AuctionKind<T::BlockNumber>,
+			reserve_price: BalanceOf<T>,
+			lease_duration: T::BlockNumber,
+			close_at: T::BlockNumber,
+		},
+		/// a bid has been placed in an english auction, reserving `deposit`
+		BidPlaced {
+			auction_index: u64,
+			bidder: T::AccountId,
+			unit_price: BalanceOf<T>,
+			deposit: BalanceOf<T>,
+		},
+		/// a sealed bid has been committed, reserving `deposit`
+		BidCommitted { auction_index: u64, bidder: T::AccountId, deposit: BalanceOf<T> },
+		/// a sealed bid has been revealed
+		BidRevealed { auction_index: u64, bidder: T::AccountId, unit_price: BalanceOf<T> },
+		/// a losing bid has been unreserved
+		BidUnreserved { auction_index: u64, bidder: T::AccountId, amount: BalanceOf<T> },
+		/// an auction has been won, its resource is leased to the winner as the rental order
+		/// `order_index`
+		AuctionWon {
+			auction_index: u64,
+			winner: T::AccountId,
+			unit_price: BalanceOf<T>,
+			order_index: u64,
+		},
+		/// an auction closed without a lease, for lack of bids or of an available resource
+		AuctionClosed { auction_index: u64 },
+		/// the threshold of a specification has been set, or removed if `None`
+		SpecificationThresholdSet {
+			specification: Specification,
+			threshold: Option<SpecificationThreshold>,
+		},
+		/// the specification of a resource has been derived again from its hardware
+		ResourceSpecificationChanged { index: u64, specification: Specification },
+		/// the node of a resource attested its hardware benchmark scores
+		HardwareAttested { index: u64, scores: HardwareScores },
+		/// the hardware of a resource does not reach any specification
+		ResourceFlagged { index: u64 },
+		/// the hardware of a flagged resource reaches a specification again
+		ResourceUnflagged { index: u64 },
+		/// an audit challenged a resource to answer before `deadline`
+		ResourceChallenged { index: u64, deadline: T::BlockNumber },
+		/// the node of a resource answered its audit challenge
+		ChallengeAnswered { index: u64 },
+		/// the node of a resource did not answer its audit challenge in time
+		ChallengeFailed { index: u64 },
+		/// the renter of an order rated its resource
+		ResourceRated { index: u64, order_index: u64, rating: u8 },
+		/// the reputation of a resource has been stored with a new score
+		ReputationUpdated { index: u64, score: Perbill },
+	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(now: T::BlockNumber) -> Weight {
-			T::DbWeight::get().reads_writes(1, 1)
+			Self::process_expiries(now)
+				.saturating_add(Self::process_heartbeat_deadlines(now))
+				.saturating_add(Self::process_era(now))
+				.saturating_add(Self::process_challenge_deadlines(now))
+				.saturating_add(Self::process_audit(now))
+				.saturating_add(Self::process_price_adjustment(now))
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::send_heartbeats(now) {
+				log::debug!(target: LOG_TARGET, "skipping heartbeats at {:?}: {}", now, e);
+			}
+			if let Err(e) = Self::send_attestations(now) {
+				log::debug!(target: LOG_TARGET, "skipping attestations at {:?}: {}", now, e);
+			}
+			if let Err(e) = Self::send_challenge_responses(now) {
+				log::debug!(target: LOG_TARGET, "skipping challenge responses at {:?}: {}", now, e);
+			}
 		}
 	}
 
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// resource does not exist
+		ResourceNotFound,
+		/// the peer id is already registered
+		PeerIdAlreadyRegistered,
+		/// the caller is not the owner of the resource
+		NotOwner,
+		/// the resource is currently in use
+		ResourceInUse,
+		/// the rentable duration must be greater than zero
+		InvalidDuration,
+		/// resource index overflow
+		IndexOverflow,
+		/// the resource can not be rented in its current status
+		ResourceNotAvailable,
+		/// providers can not rent their own resources
+		CannotRentOwnResource,
+		/// the lease would end after the rentable duration of the resource
+		ExceedsRentableDuration,
+		/// the rental price overflowed
+		PriceOverflow,
+		/// the renter can not afford the rental price
+		InsufficientBalance,
+		/// rental order does not exist
+		OrderNotFound,
+		/// the rental order has not expired yet
+		OrderNotExpired,
+		/// the rental order has already expired
+		OrderExpired,
+		/// the caller is not the renter of the order
+		NotRenter,
+		/// the previous heartbeat of the resource is too recent
+		HeartbeatTooEarly,
+		/// the heartbeat was sent too long ago
+		StaleHeartbeat,
+		/// the resource already has the required bond
+		AlreadyBonded,
+		/// the resource has no bond
+		NotBonded,
+		/// the provider can not afford the bond
+		InsufficientBond,
+		/// no unbonded collateral can be withdrawn yet
+		NoUnbondedFunds,
+		/// no payment in the asset is waiting to be claimed
+		NoUnclaimedAssets,
+		/// the era has not ended yet or is too old to be claimed
+		InvalidEra,
+		/// the provider has no unclaimed reward for the era
+		NoRewardForEra,
+		/// the reward could not be paid from the reward pot account
+		RewardPaymentFailed,
+		/// no available resource satisfies the demand
+		NoMatchingResource,
+		/// the provider has registered too many resources
+		TooManyResources,
+		/// the renter has too many active rental orders
+		TooManyOrders,
+		/// the provider has too many unbonding chunks
+		TooManyUnbondingChunks,
+		/// the expiry queues of the following blocks are full
+		QueueFull,
+		/// a dimension of the resource configuration is empty
+		InvalidDimension,
+		/// the declared hardware scores exceed the attested ones
+		ScoresAboveAttestation,
+		/// the previous hardware attestation of the resource is too recent
+		AttestationTooEarly,
+		/// the hardware attestation was signed too long ago
+		StaleAttestation,
+		/// the attested hardware of the resource does not reach any specification
+		ResourceFlagged,
+		/// the resource has no pending audit challenge
+		ChallengeNotFound,
+		/// the response does not sign the nonce of the challenge
+		InvalidChallengeResponse,
+		/// the response is not sent by the node with the peer id of the resource
+		PeerIdMismatch,
+		/// ratings range from 1 to `MAX_RATING`
+		InvalidRating,
+		/// the renter already rated the resource of the order
+		AlreadyRated,
+		/// slices of the resource can not be leased
+		ResourceNotDivisible,
+		/// a slice must hold some cpu or memory
+		InvalidSlice,
+		/// the resource does not have enough available cpus or memory for the slice
+		InsufficientCapacity,
+		/// the resource has too many concurrent leases
+		TooManyLeases,
+		/// the blocks overlap a reservation of the resource
+		ResourceReserved,
+		/// a reservation must start in a future block
+		InvalidReservation,
+		/// the resource has too many pending reservations
+		TooManyReservations,
+		/// reservation does not exist
+		ReservationNotFound,
+		/// the reservation has already started
+		ReservationStarted,
+		/// the reservation has not started yet
+		ReservationNotStarted,
+		/// the activation of the reservation or the closing of the auction could not be
+		/// scheduled
+		ScheduleFailed,
+		/// the resource is listed in an auction
+		ResourceAuctioned,
+		/// an auction must close in a future block, after the reveal phase starts
+		InvalidAuction,
+		/// auction does not exist
+		AuctionNotFound,
+		/// the auction does not take this kind of bids
+		WrongAuctionKind,
+		/// the auction no longer takes bids
+		AuctionClosed,
+		/// the auction has not closed yet
+		AuctionNotClosed,
+		/// the bid is below the reserve price or the highest bid
+		BidTooLow,
+		/// sealed bids can only be committed before the reveal phase
+		NotCommitPhase,
+		/// sealed bids can only be revealed during the reveal phase
+		NotRevealPhase,
+		/// the bidder already committed a bid in the auction
+		AlreadyCommitted,
+		/// the auction has too many sealed bids
+		TooManyBids,
+		/// the bidder has no bid in the auction
+		BidNotFound,
+		/// the bid has already been revealed
+		AlreadyRevealed,
+		/// the revealed bid does not match its commitment
+		InvalidReveal,
+		/// the price of the lease at the revealed bid exceeds its deposit
+		BidExceedsDeposit,
+		/// the price floor is above the ceiling
+		InvalidPriceBounds,
+		/// an asset is given more than one price
+		DuplicateAsset,
+		/// the resource can not be paid in the asset
+		AssetNotAccepted,
+	}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
-}
+	impl<T: Config> Pallet<T> {
+		/// register a computing resource, its specification is derived from its configuration
+		/// and hardware benchmark scores
+		#[pallet::weight(T::WeightInfo::register_resource())]
+		#[transactional]
+		pub fn register_resource(
+			origin: OriginFor<T>,
+			peer_id: BoundedStringOf<T>,
+			public_ip: BoundedStringOf<T>,
+			config: ResourceConfigOf<T>,
+			scores: HardwareScores,
+			price: BalanceOf<T>,
+			rent_duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_register_resource(
+				who,
+				peer_id,
+				public_ip,
+				config,
+				scores,
+				T::BalanceToNumber::convert(price),
+				rent_duration,
+			)?;
+			Ok(())
+		}
+
+		/// update the ip, configuration, hardware benchmark scores or unit price of a resource,
+		/// its specification is derived again when its configuration or scores change, scores
+		/// above the attested ones being rejected, and an existing bond is topped up to the new
+		/// requirement
+		#[pallet::weight(T::WeightInfo::update_resource())]
+		pub fn update_resource(
+			origin: OriginFor<T>,
+			index: u64,
+			public_ip: Option<BoundedStringOf<T>>,
+			config: Option<ResourceConfigOf<T>>,
+			scores: Option<HardwareScores>,
+			price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut resource = Self::owned_resource(&who, index)?;
+
+			// the hardware description of a rented resource must not change under its renters
+			if config.is_some() || scores.is_some() {
+				ensure!(!<ResourceLeases<T>>::contains_key(index), Error::<T>::ResourceInUse);
+			}
+
+			let old_points = Self::resource_points(&resource);
+			let old_class = resource.class();
+			let old_specification = resource.specification.clone();
+			let mut assessed = None;
+			if let Some(public_ip) = public_ip {
+				resource.public_ip = public_ip;
+			}
+			if config.is_some() || scores.is_some() {
+				if let Some(config) = config {
+					Self::ensure_valid_config(&config)?;
+					resource.config = config;
+				}
+				// declared scores can not claim more than the node attested
+				if let (Some(scores), Some(attestation)) =
+					(&scores, Self::resource_attestation(index))
+				{
+					ensure!(
+						scores.is_within(&attestation.scores),
+						Error::<T>::ScoresAboveAttestation
+					);
+				}
+				let scores = scores.clone().unwrap_or_else(|| Self::assessed_scores(index));
+				assessed = Some(Self::specification_of(&resource.config, &scores));
+			}
+			// a bonded resource can not outgrow its bond
+			let top_up = Self::resource_bond(index).and_then(|bonded| {
+				let required = Self::required_bond(&resource);
+				(bonded < required).then(|| (required, required.saturating_sub(bonded)))
+			});
+			if let Some((required, missing)) = top_up {
+				T::Currency::reserve(&who, missing).map_err(|_| Error::<T>::InsufficientBond)?;
+				<ResourceBonds<T>>::insert(index, required);
+				Self::deposit_event(Event::ResourceBonded { index, amount: required });
+			}
+			if let Some(scores) = scores {
+				<ResourceScores<T>>::insert(index, scores);
+			}
+			if let Some(specification) = assessed {
+				resource.specification = Self::flag_hardware(index, specification);
+			}
+			Self::change_class(&old_class, &resource.class(), resource.allocated_share());
+			if resource.status != ResourceStatus::Offline {
+				Self::sub_provider_points(&who, old_points, 0);
+				Self::add_provider_points(&who, Self::resource_points(&resource), 0);
+			}
+			if let Some(price) = price {
+				resource.update_resource_price(T::BalanceToNumber::convert(price));
+			}
+
+			<UnusedResources<T>>::remove(&old_specification, index);
+			Self::index_unused(&resource);
+			<Resources<T>>::insert(index, resource);
+
+			Self::deposit_event(Event::ResourceUpdated { who, index });
+			Ok(())
+		}
+
+		/// extend the rentable duration of a resource
+		#[pallet::weight(T::WeightInfo::add_resource_duration())]
+		pub fn add_resource_duration(
+			origin: OriginFor<T>,
+			index: u64,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+			let mut resource = Self::owned_resource(&who, index)?;
+
+			resource.add_resource_duration(duration);
+			<Resources<T>>::insert(index, resource);
+
+			Self::deposit_event(Event::ResourceDurationAdded { who, index, duration });
+			Ok(())
+		}
+
+		/// remove a resource that is not in use
+		#[pallet::weight(T::WeightInfo::remove_resource())]
+		pub fn remove_resource(origin: OriginFor<T>, index: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::owned_resource(&who, index)?;
+			ensure!(
+				!<ResourceLeases<T>>::contains_key(index) &&
+					resource.status != ResourceStatus::Locked,
+				Error::<T>::ResourceInUse
+			);
+			ensure!(!<ResourceReservations<T>>::contains_key(index), Error::<T>::ResourceReserved);
+			ensure!(!<ResourceAuctions<T>>::contains_key(index), Error::<T>::ResourceAuctioned);
+
+			Self::do_unbond(&who, index)?;
+			if resource.status != ResourceStatus::Offline {
+				Self::sub_provider_points(&who, Self::resource_points(&resource), 0);
+			}
+			<Resources<T>>::remove(index);
+			<UnusedResources<T>>::remove(&resource.specification, index);
+			<ResourceScores<T>>::remove(index);
+			<ResourceAttestations<T>>::remove(index);
+			<FlaggedResources<T>>::remove(index);
+			<AuditChallenges<T>>::remove(index);
+			<ResourceReputations<T>>::remove(index);
+			<DynamicPrices<T>>::remove(index);
+			<AssetPrices<T>>::remove(index);
+			Self::mutate_class_market(&resource.class(), |market| {
+				market.resources = market.resources.saturating_sub(1);
+			});
+			<PeerIds<T>>::remove(&resource.peer_id);
+			<ResourceHeartbeats<T>>::remove(index);
+			<Providers<T>>::mutate_exists(&who, |maybe_indexes| {
+				if let Some(indexes) = maybe_indexes {
+					indexes.retain(|i| *i != index);
+					if indexes.is_empty() {
+						*maybe_indexes = None;
+					}
+				}
+			});
+
+			Self::deposit_event(Event::ResourceRemoved { who, index });
+			Ok(())
+		}
+
+		/// opt a resource in dynamic pricing, its unit price following the multiplier of its class
+		/// between `floor` and `ceiling`, or out of it if `None`
+		#[pallet::weight(T::WeightInfo::set_dynamic_pricing())]
+		pub fn set_dynamic_pricing(
+			origin: OriginFor<T>,
+			index: u64,
+			bounds: Option<(BalanceOf<T>, BalanceOf<T>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::owned_resource(&who, index)?;
+			let bounds = bounds.map(|(floor, ceiling)| {
+				PriceBounds::new(
+					T::BalanceToNumber::convert(floor),
+					T::BalanceToNumber::convert(ceiling),
+				)
+			});
+			match &bounds {
+				Some(bounds) => {
+					ensure!(bounds.is_valid(), Error::<T>::InvalidPriceBounds);
+					<DynamicPrices<T>>::insert(index, bounds);
+				},
+				None => <DynamicPrices<T>>::remove(index),
+			}
 
-impl<T: Config> Pallet<T> {}
+			Self::deposit_event(Event::DynamicPricingSet { index, bounds });
+			Ok(())
+		}
+
+		/// set the assets a resource can be paid in, with its unit price in each of them,
+		/// replacing the previous ones, without any the resource is only paid in `Currency`
+		#[pallet::weight(T::WeightInfo::set_asset_prices(prices.len() as u32))]
+		pub fn set_asset_prices(
+			origin: OriginFor<T>,
+			index: u64,
+			prices: BoundedVec<(T::AssetId, AssetBalanceOf<T>), T::MaxAcceptedAssets>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::owned_resource(&who, index)?;
+			let mut accepted = AssetPricesOf::<T>::default();
+			for (asset_id, unit_price) in prices.iter() {
+				ensure!(!accepted.iter().any(|(id, _)| id == asset_id), Error::<T>::DuplicateAsset);
+				accepted
+					.try_push((*asset_id, (*unit_price).saturated_into::<u128>()))
+					.expect("as many prices as the bound; qed");
+			}
+			if accepted.is_empty() {
+				<AssetPrices<T>>::remove(index);
+			} else {
+				<AssetPrices<T>>::insert(index, accepted);
+			}
+
+			Self::deposit_event(Event::AssetPricesSet { index, prices: prices.into_inner() });
+			Ok(())
+		}
+
+		/// allow or stop leasing slices of the cpus and memory of a resource that is not in use
+		#[pallet::weight(T::WeightInfo::set_resource_divisible())]
+		pub fn set_resource_divisible(
+			origin: OriginFor<T>,
+			index: u64,
+			divisible: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut resource = Self::owned_resource(&who, index)?;
+			ensure!(!<ResourceLeases<T>>::contains_key(index), Error::<T>::ResourceInUse);
+
+			resource.divisible = divisible;
+			<Resources<T>>::insert(index, resource);
+
+			Self::deposit_event(Event::ResourceDivisibilitySet { index, divisible });
+			Ok(())
+		}
+
+		/// reserve the bond required for a resource, or top it up to the required amount
+		#[pallet::weight(T::WeightInfo::bond_resource())]
+		pub fn bond_resource(origin: OriginFor<T>, index: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::owned_resource(&who, index)?;
+			let required = Self::required_bond(&resource);
+			let bonded = Self::resource_bond(index).unwrap_or_else(Zero::zero);
+			ensure!(bonded < required, Error::<T>::AlreadyBonded);
+
+			T::Currency::reserve(&who, required.saturating_sub(bonded))
+				.map_err(|_| Error::<T>::InsufficientBond)?;
+			<ResourceBonds<T>>::insert(index, required);
+
+			Self::deposit_event(Event::ResourceBonded { index, amount: required });
+			Ok(())
+		}
+
+		/// start unbonding the bond of a resource that is not in use
+		#[pallet::weight(T::WeightInfo::unbond_resource())]
+		pub fn unbond_resource(origin: OriginFor<T>, index: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::owned_resource(&who, index)?;
+			ensure!(
+				!<ResourceLeases<T>>::contains_key(index) &&
+					resource.status != ResourceStatus::Locked,
+				Error::<T>::ResourceInUse
+			);
+			ensure!(Self::do_unbond(&who, index)?, Error::<T>::NotBonded);
+
+			Ok(())
+		}
+
+		/// unreserve the unbonded collateral whose unbonding period is over
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut amount: BalanceOf<T> = Zero::zero();
+			<Unbonding<T>>::mutate_exists(&who, |maybe_chunks| {
+				if let Some(chunks) = maybe_chunks {
+					chunks.retain(|(unlock_at, value)| {
+						if *unlock_at <= now {
+							amount = amount.saturating_add(*value);
+							false
+						} else {
+							true
+						}
+					});
+					if chunks.is_empty() {
+						*maybe_chunks = None;
+					}
+				}
+			});
+			ensure!(!amount.is_zero(), Error::<T>::NoUnbondedFunds);
+
+			T::Currency::unreserve(&who, amount);
+
+			Self::deposit_event(Event::Withdrawn { who, amount });
+			Ok(())
+		}
+
+		/// pay the reward of `provider` for an ended `era`, callable by anyone
+		#[pallet::weight(T::WeightInfo::payout_provider())]
+		pub fn payout_provider(
+			origin: OriginFor<T>,
+			provider: T::AccountId,
+			era: EraIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let current_era = Self::current_era();
+			ensure!(
+				era < current_era &&
+					era.saturating_add(T::RewardHistoryDepth::get()) >= current_era,
+				Error::<T>::InvalidEra
+			);
+			let mut claimed = Self::claimed_eras(&provider);
+			ensure!(!claimed.contains(&era), Error::<T>::NoRewardForEra);
+			let points = Self::points_at(&provider, era);
+			ensure!(!points.is_zero(), Error::<T>::NoRewardForEra);
+			let reward_pot = Self::eras_reward_pot(era).ok_or(Error::<T>::NoRewardForEra)?;
+			// only claimable eras are kept, which always leaves room for this one
+			claimed.retain(|claimed| {
+				claimed.saturating_add(T::RewardHistoryDepth::get()) >= current_era
+			});
+			claimed.try_push(era).map_err(|_| Error::<T>::NoRewardForEra)?;
+
+			let amount = multiply_by_rational(
+				T::BalanceToNumber::convert(reward_pot),
+				points,
+				Self::eras_total_points(era),
+			)
+			.map(T::NumberToBalance::convert)
+			.unwrap_or_else(|_| Zero::zero());
+
+			match T::RewardPotAccount::get() {
+				Some(pot) =>
+					T::Currency::transfer(&pot, &provider, amount, ExistenceRequirement::KeepAlive)
+						.map_err(|_| Error::<T>::RewardPaymentFailed)?,
+				None => drop(T::Currency::deposit_creating(&provider, amount)),
+			}
+			<ClaimedEras<T>>::insert(&provider, claimed);
+
+			Self::deposit_event(Event::ProviderRewarded { era, provider, amount });
+			Ok(())
+		}
+
+		/// rent a resource for `duration` blocks, the rental price is reserved until the order
+		/// is settled
+		#[pallet::weight(T::WeightInfo::rent_resource())]
+		#[transactional]
+		pub fn rent_resource(
+			origin: OriginFor<T>,
+			index: u64,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::rentable_resource(&who, index, duration)?;
+			// a partly leased resource can only be leased in slices
+			ensure!(resource.allocated.is_empty(), Error::<T>::ResourceNotAvailable);
+
+			let slice = resource.capacity();
+			let rent_unit_price = Self::unit_price_of(&resource);
+			Self::do_rent_resource(who, resource, slice, rent_unit_price, duration, None)?;
+			Ok(())
+		}
+
+		/// rent a resource for `duration` blocks paying in `asset_id` at its unit price in that
+		/// asset, the rental price is escrowed until the order is settled
+		///
+		/// the first rental escrowed in an asset also pays the minimum balance of the asset, kept
+		/// by the escrow account for good
+		#[pallet::weight(T::WeightInfo::rent_resource_in_asset())]
+		#[transactional]
+		pub fn rent_resource_in_asset(
+			origin: OriginFor<T>,
+			index: u64,
+			asset_id: T::AssetId,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::rentable_resource(&who, index, duration)?;
+			ensure!(resource.allocated.is_empty(), Error::<T>::ResourceNotAvailable);
+			let rent_unit_price =
+				Self::asset_unit_price(index, &asset_id).ok_or(Error::<T>::AssetNotAccepted)?;
+
+			let slice = resource.capacity();
+			Self::do_rent_resource(
+				who,
+				resource,
+				slice,
+				rent_unit_price,
+				duration,
+				Some(asset_id),
+			)?;
+			Ok(())
+		}
+
+		/// rent a slice of the cpus and memory of a divisible resource for `duration` blocks,
+		/// at the share of the unit price taken by the slice
+		#[pallet::weight(T::WeightInfo::rent_slice())]
+		#[transactional]
+		pub fn rent_slice(
+			origin: OriginFor<T>,
+			index: u64,
+			slice: ResourceSlice,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::rentable_resource(&who, index, duration)?;
+			ensure!(resource.divisible, Error::<T>::ResourceNotDivisible);
+			ensure!(!slice.is_empty(), Error::<T>::InvalidSlice);
+			ensure!(slice.fits_in(&resource.available()), Error::<T>::InsufficientCapacity);
+
+			let rent_unit_price = Self::slice_unit_price(&resource, &slice);
+			Self::do_rent_resource(who, resource, slice, rent_unit_price, duration, None)?;
+			Ok(())
+		}
+
+		/// rent the best available resource satisfying `demand` for `duration` blocks, at a unit
+		/// price of at most `max_unit_price`
+		#[pallet::weight(T::WeightInfo::rent_by_demand(T::MaxMatchCandidates::get()))]
+		#[transactional]
+		pub fn rent_by_demand(
+			origin: OriginFor<T>,
+			demand: ResourceDemandOf<T>,
+			max_unit_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+			policy: MatchPolicy,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+			let (resource, slice, rent_unit_price) = Self::match_resource(
+				&who,
+				&demand,
+				T::BalanceToNumber::convert(max_unit_price),
+				duration,
+				policy,
+			)
+			.ok_or(Error::<T>::NoMatchingResource)?;
+
+			Self::do_rent_resource(who, resource, slice, rent_unit_price, duration, None)?;
+			Ok(())
+		}
+
+		/// settle an expired rental order, paying the provider and releasing the resource
+		#[pallet::weight(T::WeightInfo::settle_order())]
+		pub fn settle_order(origin: OriginFor<T>, order_index: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let order = Self::rental_order(order_index).ok_or(Error::<T>::OrderNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= order.rental_info.end_of_rent, Error::<T>::OrderNotExpired);
+
+			Self::settle_expired_order(order);
+			Ok(())
+		}
+
+		/// extend an active rental order by `duration` blocks, reserving or escrowing the
+		/// additional price in the currency of the order
+		#[pallet::weight(T::WeightInfo::renew_order())]
+		#[transactional]
+		pub fn renew_order(
+			origin: OriginFor<T>,
+			order_index: u64,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+			let mut order = Self::rental_order(order_index).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.renter == who, Error::<T>::NotRenter);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < order.rental_info.end_of_rent, Error::<T>::OrderExpired);
+
+			let resource =
+				Self::resource(order.resource_index).ok_or(Error::<T>::ResourceNotFound)?;
+			let old_end_of_rent = order.rental_info.end_of_rent;
+			let new_end_of_rent = old_end_of_rent.saturating_add(duration);
+			ensure!(
+				new_end_of_rent <= resource.rental_info.end_of_rent,
+				Error::<T>::ExceedsRentableDuration
+			);
+			ensure!(
+				!Self::is_reserved(order.resource_index, old_end_of_rent, new_end_of_rent),
+				Error::<T>::ResourceReserved
+			);
+
+			let price = Self::rental_price(order.rental_info.rent_unit_price, duration)?;
+			let deposit = order.deposit.checked_add(price).ok_or(Error::<T>::PriceOverflow)?;
+			let price_balance = T::NumberToBalance::convert(price);
+			Self::escrow(&who, Self::order_asset(order_index), price)?;
+
+			order.rental_info.add_rent_duration(duration);
+			order.deposit = deposit;
+			let end_of_rent = order.rental_info.end_of_rent;
+			<RentalOrders<T>>::insert(order_index, order);
+			// the entry at the previous end block is skipped when it is processed
+			Self::enqueue::<OrderExpiries<T>, _>(end_of_rent, order_index)?;
+
+			Self::deposit_event(Event::OrderRenewed {
+				order_index,
+				end_of_rent,
+				deposit: price_balance,
+			});
+			Ok(())
+		}
+
+		/// end an active rental order early, the provider is paid for the blocks already used
+		/// and the rest of the deposit is refunded to the renter
+		#[pallet::weight(T::WeightInfo::terminate_order())]
+		pub fn terminate_order(origin: OriginFor<T>, order_index: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order = Self::rental_order(order_index).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.renter == who, Error::<T>::NotRenter);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < order.rental_info.end_of_rent, Error::<T>::OrderExpired);
+
+			let used = now.saturating_sub(order.start_of_rent);
+			let (paid, refund) = Self::do_settle_order(order, used);
+
+			Self::deposit_event(Event::OrderTerminated { order_index, renter: who, paid, refund });
+			Ok(())
+		}
+
+		/// book a whole resource for the blocks `[start, end)` at its current unit price,
+		/// reserving the `ReservationDeposit` part of the rental price
+		///
+		/// the lease starts at `start`, when the rest of the price is reserved, a renter who can
+		/// not pay for it forfeits the deposit to the provider
+		#[pallet::weight(T::WeightInfo::reserve_resource())]
+		#[transactional]
+		pub fn reserve_resource(
+			origin: OriginFor<T>,
+			index: u64,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(start > now, Error::<T>::InvalidReservation);
+			ensure!(end > start, Error::<T>::InvalidDuration);
+			let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+			ensure!(!<FlaggedResources<T>>::contains_key(index), Error::<T>::ResourceFlagged);
+			ensure!(resource.account_id != who, Error::<T>::CannotRentOwnResource);
+			ensure!(end <= resource.rental_info.end_of_rent, Error::<T>::ExceedsRentableDuration);
+			ensure!(!Self::is_reserved(index, start, end), Error::<T>::ResourceReserved);
+			ensure!(!<ResourceAuctions<T>>::contains_key(index), Error::<T>::ResourceAuctioned);
+			// the leases of the resource must end before the reservation starts
+			ensure!(
+				Self::resource_leases(index)
+					.unwrap_or_default()
+					.into_iter()
+					.filter_map(Self::rental_order)
+					.all(|order| order.rental_info.end_of_rent <= start),
+				Error::<T>::ResourceInUse
+			);
+
+			let rent_unit_price = Self::unit_price_of(&resource);
+			let price = Self::rental_price(rent_unit_price, end.saturating_sub(start))?;
+			let deposit = T::ReservationDeposit::get() * price;
+			let deposit_balance = T::NumberToBalance::convert(deposit);
+
+			let reservation_index = Self::reservation_index();
+			let next_reservation_index =
+				reservation_index.checked_add(1).ok_or(Error::<T>::IndexOverflow)?;
+			<ResourceReservations<T>>::try_append(index, (start, end))
+				.map_err(|_| Error::<T>::TooManyReservations)?;
+			T::Currency::reserve(&who, deposit_balance)
+				.map_err(|_| Error::<T>::InsufficientBalance)?;
+			T::Scheduler::schedule_named(
+				(RESERVATION_TASK_ID, reservation_index).encode(),
+				DispatchTime::At(start),
+				None,
+				TASK_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				Call::activate_reservation { reservation_index }.into(),
+			)
+			.map_err(|_| Error::<T>::ScheduleFailed)?;
+
+			let reservation = Reservation::new(
+				reservation_index,
+				index,
+				who.clone(),
+				resource.account_id,
+				rent_unit_price,
+				start,
+				end,
+				deposit,
+			);
+			<Reservations<T>>::insert(reservation_index, reservation);
+			<ReservationIndex<T>>::put(next_reservation_index);
+
+			Self::deposit_event(Event::ResourceReserved {
+				renter: who,
+				resource_index: index,
+				reservation_index,
+				start,
+				end,
+				deposit: deposit_balance,
+			});
+			Ok(())
+		}
+
+		/// cancel a reservation before it starts, refunding its deposit
+		#[pallet::weight(T::WeightInfo::cancel_reservation())]
+		pub fn cancel_reservation(origin: OriginFor<T>, reservation_index: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let reservation =
+				Self::reservation(reservation_index).ok_or(Error::<T>::ReservationNotFound)?;
+			ensure!(reservation.renter == who, Error::<T>::NotRenter);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < reservation.start, Error::<T>::ReservationStarted);
+
+			T::Scheduler::cancel_named((RESERVATION_TASK_ID, reservation_index).encode())
+				.map_err(|_| Error::<T>::ReservationStarted)?;
+			T::Currency::unreserve(&who, T::NumberToBalance::convert(reservation.deposit));
+			Self::remove_reservation(&reservation);
+
+			Self::deposit_event(Event::ReservationCancelled { reservation_index, renter: who });
+			Ok(())
+		}
+
+		/// start the lease of a reservation, dispatched by the scheduler at its first block
+		///
+		/// the leases of the resource that have ended are settled first, the deposit is forfeited
+		/// to the provider if the renter can not pay for the lease and refunded if the lease can
+		/// not start for any other reason
+		#[pallet::weight(T::WeightInfo::activate_reservation())]
+		pub fn activate_reservation(
+			origin: OriginFor<T>,
+			reservation_index: u64,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let reservation =
+				Self::reservation(reservation_index).ok_or(Error::<T>::ReservationNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= reservation.start, Error::<T>::ReservationNotStarted);
+			Self::remove_reservation(&reservation);
+
+			let index = reservation.resource_index;
+			for order in Self::resource_leases(index)
+				.unwrap_or_default()
+				.into_iter()
+				.filter_map(Self::rental_order)
+				.filter(|order| order.rental_info.end_of_rent <= now)
+			{
+				Self::settle_expired_order(order);
+			}
+
+			let renter = reservation.renter;
+			let deposit = T::NumberToBalance::convert(reservation.deposit);
+			let resource = match Self::resource(index) {
+				Some(resource)
+					if resource.status == ResourceStatus::Unused &&
+						!<ResourceLeases<T>>::contains_key(index) &&
+						!<FlaggedResources<T>>::contains_key(index) &&
+						now < reservation.end =>
+					resource,
+				_ => {
+					T::Currency::unreserve(&renter, deposit);
+					Self::deposit_event(Event::ReservationFailed { reservation_index, renter });
+					return Ok(())
+				},
+			};
+
+			// the deposit is part of the price reserved for the lease
+			let leased = with_transaction(|| {
+				T::Currency::unreserve(&renter, deposit);
+				let slice = resource.capacity();
+				match Self::do_rent_resource(
+					renter.clone(),
+					resource,
+					slice,
+					reservation.rent_unit_price,
+					reservation.end.saturating_sub(now),
+					None,
+				) {
+					Ok(order_index) => TransactionOutcome::Commit(Ok(order_index)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			});
+
+			match leased {
+				Ok(order_index) => Self::deposit_event(Event::ReservationActivated {
+					reservation_index,
+					order_index,
+				}),
+				Err(e) if e != Error::<T>::InsufficientBalance.into() => {
+					log::debug!(
+						target: LOG_TARGET,
+						"lease of reservation {} can not start: {:?}",
+						reservation_index,
+						e,
+					);
+					T::Currency::unreserve(&renter, deposit);
+					Self::deposit_event(Event::ReservationFailed { reservation_index, renter });
+				},
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"renter of reservation {} can not start its lease: {:?}",
+						reservation_index,
+						e,
+					);
+					let provider = reservation.provider;
+					let amount = match T::Currency::repatriate_reserved(
+						&renter,
+						&provider,
+						deposit,
+						BalanceStatus::Free,
+					) {
+						Ok(remaining) => deposit.saturating_sub(remaining),
+						Err(_) => {
+							T::Currency::unreserve(&renter, deposit);
+							Zero::zero()
+						},
+					};
+					Self::deposit_event(Event::ReservationForfeited {
+						reservation_index,
+						renter,
+						provider,
+						amount,
+					});
+				},
+			}
+			Ok(())
+		}
+
+		/// list a resource in an auction closing at `close_at` instead of renting it at its unit
+		/// price
+		///
+		/// the highest bid of at least `reserve_price` when the auction closes becomes the unit
+		/// price of a lease of the whole resource for `lease_duration` blocks
+		#[pallet::weight(T::WeightInfo::list_auction())]
+		#[transactional]
+		pub fn list_auction(
+			origin: OriginFor<T>,
+			index: u64,
+			kind: AuctionKind<T::BlockNumber>,
+			reserve_price: BalanceOf<T>,
+			lease_duration: T::BlockNumber,
+			close_at: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let resource = Self::owned_resource(&who, index)?;
+			ensure!(!<ResourceAuctions<T>>::contains_key(index), Error::<T>::ResourceAuctioned);
+			ensure!(!<ResourceLeases<T>>::contains_key(index), Error::<T>::ResourceInUse);
+			ensure!(resource.status == ResourceStatus::Unused, Error::<T>::ResourceNotAvailable);
+			ensure!(!<FlaggedResources<T>>::contains_key(index), Error::<T>::ResourceFlagged);
+			ensure!(!lease_duration.is_zero(), Error::<T>::InvalidDuration);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let valid = match kind {
+				AuctionKind::English => close_at > now,
+				AuctionKind::SealedBid { reveal_start } =>
+					reveal_start > now && close_at > reveal_start,
+			};
+			ensure!(valid, Error::<T>::InvalidAuction);
+			let end_of_rent = close_at.saturating_add(lease_duration);
+			ensure!(
+				end_of_rent <= resource.rental_info.end_of_rent,
+				Error::<T>::ExceedsRentableDuration
+			);
+			ensure!(!Self::is_reserved(index, now, end_of_rent), Error::<T>::ResourceReserved);
+			let reserve_unit_price = T::BalanceToNumber::convert(reserve_price);
+			Self::rental_price(reserve_unit_price, lease_duration)?;
+
+			let auction_index = Self::auction_index();
+			let next_auction_index =
+				auction_index.checked_add(1).ok_or(Error::<T>::IndexOverflow)?;
+			T::Scheduler::schedule_named(
+				(AUCTION_TASK_ID, auction_index).encode(),
+				DispatchTime::At(close_at),
+				None,
+				TASK_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				Call::close_auction { auction_index }.into(),
+			)
+			.map_err(|_| Error::<T>::ScheduleFailed)?;
+
+			let auction = Auction::new(
+				auction_index,
+				index,
+				who,
+				kind.clone(),
+				reserve_unit_price,
+				lease_duration,
+				close_at,
+			);
+			<Auctions<T>>::insert(auction_index, auction);
+			<ResourceAuctions<T>>::insert(index, auction_index);
+			<AuctionIndex<T>>::put(next_auction_index);
+
+			Self::deposit_event(Event::AuctionListed {
+				resource_index: index,
+				auction_index,
+				kind,
+				reserve_price,
+				lease_duration,
+				close_at,
+			});
+			Ok(())
+		}
+
+		/// bid `unit_price` in an english auction, reserving the price of the lease until the
+		/// bid is outbid or the auction closes
+		#[pallet::weight(T::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			auction_index: u64,
+			unit_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::open_auction(&who, auction_index)?;
+			ensure!(auction.kind == AuctionKind::English, Error::<T>::WrongAuctionKind);
+			let bid_unit_price = T::BalanceToNumber::convert(unit_price);
+			ensure!(Self::outbids(&auction, bid_unit_price), Error::<T>::BidTooLow);
+			let deposit = T::NumberToBalance::convert(Self::rental_price(
+				bid_unit_price,
+				auction.lease_duration,
+			)?);
+
+			if let Some((bidder, _)) = auction.best_bid.take() {
+				Self::unreserve_bid(auction_index, bidder);
+			}
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+			<AuctionBids<T>>::insert(auction_index, &who, (None::<T::Hash>, deposit));
+			auction.best_bid = Some((who.clone(), bid_unit_price));
+			<Auctions<T>>::insert(auction_index, auction);
+
+			Self::deposit_event(Event::BidPlaced {
+				auction_index,
+				bidder: who,
+				unit_price,
+				deposit,
+			});
+			Ok(())
+		}
+
+		/// commit a sealed bid before the reveal phase of a sealed-bid auction, `commitment`
+		/// being the [`Pallet::bid_commitment`] of the bid
+		///
+		/// `deposit` is reserved until the auction closes or the bid is revealed as a losing
+		/// one, and must cover the price of the lease at the revealed unit price
+		#[pallet::weight(T::WeightInfo::commit_bid())]
+		#[transactional]
+		pub fn commit_bid(
+			origin: OriginFor<T>,
+			auction_index: u64,
+			commitment: T::Hash,
+			deposit: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::open_auction(&who, auction_index)?;
+			let reveal_start = match auction.kind {
+				AuctionKind::SealedBid { reveal_start } => reveal_start,
+				AuctionKind::English => return Err(Error::<T>::WrongAuctionKind.into()),
+			};
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < reveal_start, Error::<T>::NotCommitPhase);
+			ensure!(
+				!<AuctionBids<T>>::contains_key(auction_index, &who),
+				Error::<T>::AlreadyCommitted
+			);
+			ensure!(auction.commitments < T::MaxBidsPerAuction::get(), Error::<T>::TooManyBids);
+
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientBalance)?;
+			<AuctionBids<T>>::insert(auction_index, &who, (Some(commitment), deposit));
+			auction.commitments += 1;
+			<Auctions<T>>::insert(auction_index, auction);
+
+			Self::deposit_event(Event::BidCommitted { auction_index, bidder: who, deposit });
+			Ok(())
+		}
+
+		/// reveal a sealed bid during the reveal phase of its auction, the deposit of a bid that
+		/// is not the highest one is unreserved right away
+		#[pallet::weight(T::WeightInfo::reveal_bid())]
+		pub fn reveal_bid(
+			origin: OriginFor<T>,
+			auction_index: u64,
+			unit_price: BalanceOf<T>,
+			salt: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::open_auction(&who, auction_index)?;
+			let reveal_start = match auction.kind {
+				AuctionKind::SealedBid { reveal_start } => reveal_start,
+				AuctionKind::English => return Err(Error::<T>::WrongAuctionKind.into()),
+			};
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= reveal_start, Error::<T>::NotRevealPhase);
+			let (commitment, deposit) =
+				Self::auction_bid(auction_index, &who).ok_or(Error::<T>::BidNotFound)?;
+			let commitment = commitment.ok_or(Error::<T>::AlreadyRevealed)?;
+			ensure!(
+				Self::bid_commitment(&who, unit_price, &salt) == commitment,
+				Error::<T>::InvalidReveal
+			);
+			let bid_unit_price = T::BalanceToNumber::convert(unit_price);
+			ensure!(bid_unit_price >= auction.reserve_price, Error::<T>::BidTooLow);
+			let price = Self::rental_price(bid_unit_price, auction.lease_duration)?;
+			ensure!(price <= T::BalanceToNumber::convert(deposit), Error::<T>::BidExceedsDeposit);
+
+			Self::deposit_event(Event::BidRevealed {
+				auction_index,
+				bidder: who.clone(),
+				unit_price,
+			});
+			// ties are won by the bid revealed first
+			if Self::outbids(&auction, bid_unit_price) {
+				<AuctionBids<T>>::insert(auction_index, &who, (None::<T::Hash>, deposit));
+				if let Some((bidder, _)) = auction.best_bid.replace((who, bid_unit_price)) {
+					Self::unreserve_bid(auction_index, bidder);
+				}
+				<Auctions<T>>::insert(auction_index, auction);
+			} else {
+				Self::unreserve_bid(auction_index, who);
+			}
+			Ok(())
+		}
+
+		/// close an auction, dispatched by the scheduler at its closing block
+		///
+		/// the bids left are unreserved, and the winner leases the whole resource at its unit
+		/// price if the resource is still available
+		#[pallet::weight(T::WeightInfo::close_auction(T::MaxBidsPerAuction::get()))]
+		pub fn close_auction(origin: OriginFor<T>, auction_index: u64) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let auction = Self::auction(auction_index).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= auction.close_at, Error::<T>::AuctionNotClosed);
+			<Auctions<T>>::remove(auction_index);
+			<ResourceAuctions<T>>::remove(auction.resource_index);
+
+			let winner = auction.best_bid.and_then(|(bidder, unit_price)| {
+				<AuctionBids<T>>::take(auction_index, &bidder)
+					.map(|(_, deposit)| (bidder, unit_price, deposit))
+			});
+			// the bids left have not been revealed
+			for (bidder, (_, amount)) in <AuctionBids<T>>::drain_prefix(auction_index) {
+				T::Currency::unreserve(&bidder, amount);
+				Self::deposit_event(Event::BidUnreserved { auction_index, bidder, amount });
+			}
+
+			let index = auction.resource_index;
+			let available = Self::resource(index).filter(|resource| {
+				resource.status == ResourceStatus::Unused &&
+					!<ResourceLeases<T>>::contains_key(index) &&
+					!<FlaggedResources<T>>::contains_key(index)
+			});
+			let ((winner, unit_price, deposit), resource) = match (winner, available) {
+				(Some(winner), Some(resource)) => (winner, resource),
+				(winner, _) => {
+					if let Some((bidder, _, amount)) = winner {
+						T::Currency::unreserve(&bidder, amount);
+						Self::deposit_event(Event::BidUnreserved { auction_index, bidder, amount });
+					}
+					Self::deposit_event(Event::AuctionClosed { auction_index });
+					return Ok(())
+				},
+			};
+
+			// the deposit of the winning bid is the price reserved for the lease
+			let leased = with_transaction(|| {
+				T::Currency::unreserve(&winner, deposit);
+				let slice = resource.capacity();
+				match Self::do_rent_resource(
+					winner.clone(),
+					resource,
+					slice,
+					unit_price,
+					auction.lease_duration,
+					None,
+				) {
+					Ok(order_index) => TransactionOutcome::Commit(Ok(order_index)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			});
+
+			match leased {
+				Ok(order_index) => Self::deposit_event(Event::AuctionWon {
+					auction_index,
+					winner,
+					unit_price: T::NumberToBalance::convert(unit_price),
+					order_index,
+				}),
+				Err(e) => {
+					log::debug!(
+						target: LOG_TARGET,
+						"winner of auction {} can not start its lease: {:?}",
+						auction_index,
+						e,
+					);
+					T::Currency::unreserve(&winner, deposit);
+					Self::deposit_event(Event::BidUnreserved {
+						auction_index,
+						bidder: winner,
+						amount: deposit,
+					});
+					Self::deposit_event(Event::AuctionClosed { auction_index });
+				},
+			}
+			Ok(())
+		}
+
+		/// record a heartbeat of a resource, bringing it back if it was offline
+		// the signature is verified in `validate_unsigned`
+		#[pallet::weight(T::WeightInfo::validate_unsigned_and_then_heartbeat())]
+		#[transactional]
+		pub fn heartbeat(
+			origin: OriginFor<T>,
+			heartbeat: HeartbeatPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				heartbeat.block_number.saturating_add(T::HeartbeatInterval::get()) > now,
+				Error::<T>::StaleHeartbeat
+			);
+			let index = heartbeat.resource_index;
+			let mut resource = Self::check_heartbeat(index, &heartbeat.public.into_account(), now)?;
+
+			if resource.status == ResourceStatus::Offline {
+				let offline_since = Self::resource_heartbeat(index).unwrap_or(now);
+				resource
+					.rental_statistics
+					.add_fault_duration(now.saturating_sub(offline_since).saturated_into::<u32>());
+				let status =
+					if <ResourceLeases<T>>::contains_key(index) && resource.is_fully_allocated() {
+						ResourceStatus::Inuse
+					} else {
+						ResourceStatus::Unused
+					};
+				resource.update_status(status);
+				Self::add_provider_points(
+					&resource.account_id,
+					Self::resource_points(&resource),
+					0,
+				);
+				Self::index_unused(&resource);
+				<Resources<T>>::insert(index, resource);
+				Self::deposit_event(Event::ResourceOnline { index, status });
+			}
+
+			Self::note_heartbeat(index, now);
+
+			Self::deposit_event(Event::HeartbeatReceived { index });
+			Ok(())
+		}
+
+		/// set the threshold of a specification, or remove it with `None`
+		///
+		/// registered resources keep their specification until it is derived again
+		#[pallet::weight(T::WeightInfo::set_specification_threshold())]
+		pub fn set_specification_threshold(
+			origin: OriginFor<T>,
+			specification: Specification,
+			threshold: Option<SpecificationThreshold>,
+		) -> DispatchResult {
+			T::ThresholdOrigin::ensure_origin(origin)?;
+
+			match &threshold {
+				Some(threshold) => <SpecificationThresholds<T>>::insert(&specification, threshold),
+				None => <SpecificationThresholds<T>>::remove(&specification),
+			}
+
+			Self::deposit_event(Event::SpecificationThresholdSet { specification, threshold });
+			Ok(())
+		}
+
+		/// derive the specification of a resource that is not in use again from the current
+		/// thresholds and its attested scores if any, a resource no longer reaching the `General`
+		/// threshold stays `General` and is flagged
+		#[pallet::weight(T::WeightInfo::refresh_specification())]
+		pub fn refresh_specification(origin: OriginFor<T>, index: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+			ensure!(!<ResourceLeases<T>>::contains_key(index), Error::<T>::ResourceInUse);
+
+			let scores = Self::assessed_scores(index);
+			let specification =
+				Self::flag_hardware(index, Self::specification_of(&resource.config, &scores));
+			if specification != resource.specification {
+				Self::change_specification(index, resource, specification);
+			}
+			Ok(())
+		}
+
+		/// record the hardware benchmark scores attested by the node of a resource, downgrading
+		/// the resource if they no longer reach its specification, down to `General` and flagged
+		/// if they reach none
+		// the signature is verified in `validate_unsigned`
+		#[pallet::weight(T::WeightInfo::validate_unsigned_and_then_attest_hardware())]
+		pub fn attest_hardware(
+			origin: OriginFor<T>,
+			attestation: AttestationPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				attestation.block_number.saturating_add(T::AttestationInterval::get()) > now,
+				Error::<T>::StaleAttestation
+			);
+			let index = attestation.resource_index;
+			let resource = Self::check_attestation(index, &attestation.public.into_account(), now)?;
+			let scores = attestation.scores;
+
+			let specification =
+				Self::flag_hardware(index, Self::specification_of(&resource.config, &scores));
+			// attestations only downgrade, an upgrade is asked with `refresh_specification`
+			if specification < resource.specification {
+				Self::change_specification(index, resource, specification);
+			}
+			<ResourceAttestations<T>>::insert(
+				index,
+				HardwareAttestation {
+					scores: scores.clone(),
+					block_number: now,
+					timestamp: T::UnixTime::now().as_millis().saturated_into(),
+				},
+			);
+
+			Self::deposit_event(Event::HardwareAttested { index, scores });
+			Ok(())
+		}
+
+		/// answer the pending audit challenge of a resource
+		// the signature is verified in `validate_unsigned`
+		#[pallet::weight(T::WeightInfo::validate_unsigned_and_then_answer_challenge())]
+		pub fn answer_challenge(
+			origin: OriginFor<T>,
+			response: ChallengeResponsePayload<T::Public, T::Hash>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let index = response.resource_index;
+			Self::check_challenge_response(
+				index,
+				&response.public.into_account(),
+				&response.peer_id,
+				&response.nonce,
+			)?;
+			<AuditChallenges<T>>::remove(index);
+
+			Self::deposit_event(Event::ChallengeAnswered { index });
+			Ok(())
+		}
+
+		/// rate the resource of an active order, once per order, from 1 to `MAX_RATING`
+		#[pallet::weight(T::WeightInfo::rate_order())]
+		pub fn rate_order(origin: OriginFor<T>, order_index: u64, rating: u8) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let order = Self::rental_order(order_index).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.renter == who, Error::<T>::NotRenter);
+			ensure!((1..=MAX_RATING).contains(&rating), Error::<T>::InvalidRating);
+			ensure!(!<OrderRatings<T>>::contains_key(order_index), Error::<T>::AlreadyRated);
+			let index = order.resource_index;
+			let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+
+			<OrderRatings<T>>::insert(order_index, rating);
+			let mut reputation = Self::reputation_of(&resource);
+			reputation.rate(rating);
+			reputation.update_score();
+			Self::store_reputation(index, reputation);
+
+			Self::deposit_event(Event::ResourceRated { index, order_index, rating });
+			Ok(())
+		}
+
+		/// store the reputation of a resource counting its latest rental statistics
+		#[pallet::weight(T::WeightInfo::refresh_reputation())]
+		pub fn refresh_reputation(origin: OriginFor<T>, index: u64) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+			Self::store_reputation(index, Self::reputation_of(&resource));
+			Ok(())
+		}
+
+		/// transfer the payments in `asset_id` that could not be transferred to the caller when
+		/// they were made
+		#[pallet::weight(T::WeightInfo::claim_assets())]
+		#[transactional]
+		pub fn claim_assets(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let amount = <UnclaimedAssets<T>>::take(&who, asset_id);
+			ensure!(!amount.is_zero(), Error::<T>::NoUnclaimedAssets);
+			let amount: AssetBalanceOf<T> =
+				amount.try_into().map_err(|_| Error::<T>::PriceOverflow)?;
+			T::Assets::transfer(asset_id, &Self::escrow_account(), &who, amount, true)?;
+
+			Self::deposit_event(Event::AssetsClaimed { who, asset_id, amount });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::attest_hardware { attestation, signature } = call {
+				let now = <frame_system::Pallet<T>>::block_number();
+				if attestation.block_number > now {
+					return InvalidTransaction::Future.into()
+				}
+				if attestation.block_number.saturating_add(T::AttestationInterval::get()) <= now {
+					return InvalidTransaction::Stale.into()
+				}
+
+				match Self::check_attestation(
+					attestation.resource_index,
+					&attestation.public.clone().into_account(),
+					now,
+				) {
+					Ok(_) => (),
+					Err(Error::<T>::AttestationTooEarly) => return InvalidTransaction::Stale.into(),
+					Err(_) => return InvalidTransaction::BadProof.into(),
+				}
+
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(attestation, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into()
+				}
+
+				return ValidTransaction::with_tag_prefix("ProviderAttestation")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((attestation.resource_index, attestation.block_number))
+					.longevity(T::AttestationInterval::get().saturated_into::<u64>())
+					.propagate(true)
+					.build()
+			}
+
+			if let Call::answer_challenge { response, signature } = call {
+				let index = response.resource_index;
+				match Self::check_challenge_response(
+					index,
+					&response.public.clone().into_account(),
+					&response.peer_id,
+					&response.nonce,
+				) {
+					Ok(_) => (),
+					Err(Error::<T>::ChallengeNotFound) => return InvalidTransaction::Stale.into(),
+					Err(_) => return InvalidTransaction::BadProof.into(),
+				}
+
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(response, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into()
+				}
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				let deadline =
+					Self::audit_challenge(index).map(|challenge| challenge.deadline).unwrap_or(now);
+				return ValidTransaction::with_tag_prefix("ProviderChallenge")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((index, response.nonce))
+					.longevity(deadline.saturating_sub(now).saturated_into::<u64>())
+					.propagate(true)
+					.build()
+			}
+
+			if let Call::heartbeat { heartbeat, signature } = call {
+				let now = <frame_system::Pallet<T>>::block_number();
+				if heartbeat.block_number > now {
+					return InvalidTransaction::Future.into()
+				}
+				if heartbeat.block_number.saturating_add(T::HeartbeatInterval::get()) <= now {
+					return InvalidTransaction::Stale.into()
+				}
+
+				match Self::check_heartbeat(
+					heartbeat.resource_index,
+					&heartbeat.public.clone().into_account(),
+					now,
+				) {
+					Ok(_) => (),
+					Err(Error::<T>::HeartbeatTooEarly) => return InvalidTransaction::Stale.into(),
+					Err(_) => return InvalidTransaction::BadProof.into(),
+				}
+
+				// check signature (this is expensive so we do it last).
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(heartbeat, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into()
+				}
+
+				ValidTransaction::with_tag_prefix("ProviderHeartbeat")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((heartbeat.resource_index, heartbeat.block_number))
+					.longevity(T::HeartbeatInterval::get().saturated_into::<u64>())
+					.propagate(true)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// register a new resource for `who`, returning its index
+	fn do_register_resource(
+		who: T::AccountId,
+		peer_id: BoundedStringOf<T>,
+		public_ip: BoundedStringOf<T>,
+		config: ResourceConfigOf<T>,
+		scores: HardwareScores,
+		rent_unit_price: u128,
+		rent_duration: T::BlockNumber,
+	) -> Result<u64, DispatchError> {
+		ensure!(!rent_duration.is_zero(), Error::<T>::InvalidDuration);
+		ensure!(!<PeerIds<T>>::contains_key(&peer_id), Error::<T>::PeerIdAlreadyRegistered);
+		Self::ensure_valid_config(&config)?;
+		let assessed = Self::specification_of(&config, &scores);
+		let specification = assessed.clone().unwrap_or(Specification::General);
+
+		let index = Self::resource_index();
+		let next_index = index.checked_add(1).ok_or(Error::<T>::IndexOverflow)?;
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let rental_info = ResourceRentalInfo::new(
+			rent_unit_price,
+			rent_duration,
+			now.saturating_add(rent_duration),
+		);
+		let resource = ComputingResource::new(
+			index,
+			who.clone(),
+			peer_id.clone(),
+			config,
+			ResourceRentalStatistics::new(0, 0, 0, 0),
+			rental_info,
+			ResourceStatus::Unused,
+			public_ip,
+			specification,
+			now,
+		);
+
+		<Providers<T>>::try_append(&who, index).map_err(|_| Error::<T>::TooManyResources)?;
+		Self::flag_hardware(index, assessed);
+		Self::note_heartbeat(index, now);
+		Self::add_provider_points(&who, Self::resource_points(&resource), 0);
+		Self::mutate_class_market(&resource.class(), |market| {
+			market.resources = market.resources.saturating_add(1);
+		});
+		Self::index_unused(&resource);
+		<Resources<T>>::insert(index, resource);
+		<ResourceScores<T>>::insert(index, scores);
+		<PeerIds<T>>::insert(&peer_id, index);
+		<ResourceIndex<T>>::put(next_index);
+
+		Self::deposit_event(Event::ResourceRegistered { who, index, peer_id });
+		Ok(index)
+	}
+
+	/// store a resource with the entries indexing it: its peer id, its provider, its class market,
+	/// a heartbeat at `now` and its points
+	pub(crate) fn insert_resource(
+		index: u64,
+		resource: &ResourceOf<T>,
+		now: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(
+			!<PeerIds<T>>::contains_key(&resource.peer_id),
+			Error::<T>::PeerIdAlreadyRegistered
+		);
+		<Providers<T>>::try_append(&resource.account_id, index)
+			.map_err(|_| Error::<T>::TooManyResources)?;
+		<PeerIds<T>>::insert(&resource.peer_id, index);
+		Self::note_heartbeat(index, now);
+		Self::mutate_class_market(&resource.class(), |market| {
+			market.resources = market.resources.saturating_add(1);
+			market.reallocate(Perbill::zero(), resource.allocated_share());
+		});
+		if resource.status != ResourceStatus::Offline {
+			Self::add_provider_points(&resource.account_id, Self::resource_points(resource), 0);
+		}
+		Self::index_unused(resource);
+		<Resources<T>>::insert(index, resource);
+		Ok(())
+	}
+
+	/// get a resource that `who` can rent for `duration` blocks
+	fn rentable_resource(
+		who: &T::AccountId,
+		index: u64,
+		duration: T::BlockNumber,
+	) -> Result<ResourceOf<T>, DispatchError> {
+		ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
+		let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+		ensure!(resource.status == ResourceStatus::Unused, Error::<T>::ResourceNotAvailable);
+		ensure!(!<FlaggedResources<T>>::contains_key(index), Error::<T>::ResourceFlagged);
+		ensure!(resource.account_id != *who, Error::<T>::CannotRentOwnResource);
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let end_of_rent = now.saturating_add(duration);
+		ensure!(
+			end_of_rent <= resource.rental_info.end_of_rent,
+			Error::<T>::ExceedsRentableDuration
+		);
+		ensure!(!Self::is_reserved(index, now, end_of_rent), Error::<T>::ResourceReserved);
+		ensure!(!<ResourceAuctions<T>>::contains_key(index), Error::<T>::ResourceAuctioned);
+		Ok(resource)
+	}
+
+	/// whether a pending reservation of a resource shares a block with `[start, end)`
+	fn is_reserved(index: u64, start: T::BlockNumber, end: T::BlockNumber) -> bool {
+		Self::resource_reservations(index)
+			.unwrap_or_default()
+			.iter()
+			.any(|(reserved_start, reserved_end)| *reserved_start < end && start < *reserved_end)
+	}
+
+	/// open a lease of `slice` for `duration` blocks on an available resource at
+	/// `rent_unit_price`, escrowing its price in `asset_id` from `who` or reserving it in
+	/// `Currency` if `None`, returns the index of the order
+	fn do_rent_resource(
+		who: T::AccountId,
+		mut resource: ResourceOf<T>,
+		slice: ResourceSlice,
+		rent_unit_price: u128,
+		duration: T::BlockNumber,
+		asset_id: Option<T::AssetId>,
+	) -> Result<u64, DispatchError> {
+		let index = resource.index;
+		let now = <frame_system::Pallet<T>>::block_number();
+		let end_of_rent = now.saturating_add(duration);
+
+		let deposit = Self::rental_price(rent_unit_price, duration)?;
+		let deposit_balance = T::NumberToBalance::convert(deposit);
+
+		let order_index = Self::order_index();
+		let next_order_index = order_index.checked_add(1).ok_or(Error::<T>::IndexOverflow)?;
+		<RenterOrders<T>>::try_append(&who, order_index).map_err(|_| Error::<T>::TooManyOrders)?;
+		<ResourceLeases<T>>::try_append(index, order_index)
+			.map_err(|_| Error::<T>::TooManyLeases)?;
+		Self::enqueue::<OrderExpiries<T>, _>(end_of_rent, order_index)?;
+		Self::escrow(&who, asset_id, deposit)?;
+
+		let order = RentalOrder::new(
+			order_index,
+			index,
+			who.clone(),
+			resource.account_id.clone(),
+			slice,
+			ResourceRentalInfo::new(rent_unit_price, duration, end_of_rent),
+			now,
+			deposit,
+		);
+
+		let allocated_share = resource.allocated_share();
+		resource.allocated = resource.allocated.saturating_add(slice);
+		Self::mutate_class_market(&resource.class(), |market| {
+			market.reallocate(allocated_share, resource.allocated_share());
+		});
+		if resource.is_fully_allocated() {
+			resource.update_status(ResourceStatus::Inuse);
+			Self::index_unused(&resource);
+		}
+		resource.rental_statistics.add_rental_count();
+
+		<Resources<T>>::insert(index, resource);
+		<RentalOrders<T>>::insert(order_index, order);
+		if let Some(asset_id) = asset_id {
+			<OrderAssets<T>>::insert(order_index, asset_id);
+		}
+		<OrderIndex<T>>::put(next_order_index);
+
+		Self::deposit_event(Event::ResourceRented {
+			renter: who,
+			resource_index: index,
+			order_index,
+			slice,
+			end_of_rent,
+			deposit: deposit_balance,
+			asset_id,
+		});
+		Ok(order_index)
+	}
+
+	/// unit price of leasing `slice` of a resource, the share of the resource unit price taken
+	/// by the slice
+	fn slice_unit_price(resource: &ResourceOf<T>, slice: &ResourceSlice) -> u128 {
+		let capacity = resource.capacity();
+		let unit_price = Self::unit_price_of(resource);
+		if *slice == capacity {
+			return unit_price
+		}
+		slice.share_of(&capacity) * unit_price
+	}
+
+	/// current unit price of a resource, its own one moved by the multiplier of its class and
+	/// kept within its bounds if it is dynamically priced
+	fn unit_price_of(resource: &ResourceOf<T>) -> u128 {
+		let unit_price = resource.rental_info.rent_unit_price;
+		match Self::price_bounds(resource.index) {
+			Some(bounds) => {
+				let multiplier = Self::class_market(resource.class())
+					.map(|market| market.multiplier)
+					.unwrap_or_else(FixedU128::one);
+				bounds.clamp(multiplier.saturating_mul_int(unit_price))
+			},
+			None => unit_price,
+		}
+	}
+
+	/// add a resource to the unused resources of its specification if it is unused, remove it
+	/// otherwise
+	fn index_unused(resource: &ResourceOf<T>) {
+		if resource.status == ResourceStatus::Unused {
+			<UnusedResources<T>>::insert(&resource.specification, resource.index, ());
+		} else {
+			<UnusedResources<T>>::remove(&resource.specification, resource.index);
+		}
+	}
+
+	/// find the best resource for `demand` that can be leased to `who` among the first
+	/// `MaxMatchCandidates` unused ones of a high enough specification, with the slice to lease
+	///
+	/// no more candidates are read whether they match or not, so the weight of `rent_by_demand`
+	/// bounds the reads
+	///
+	/// divisible resources lease the demanded cpus and memory out of their available ones, the
+	/// others are leased whole
+	fn match_resource(
+		who: &T::AccountId,
+		demand: &ResourceDemandOf<T>,
+		max_unit_price: u128,
+		duration: T::BlockNumber,
+		policy: MatchPolicy,
+	) -> Option<(ResourceOf<T>, ResourceSlice, u128)> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let end_of_rent = now.saturating_add(duration);
+		let demanded = ResourceSlice::new(demand.cpu, demand.memory);
+
+		let candidates =
+			[Specification::General, Specification::Enhanced, Specification::HighRanking]
+				.into_iter()
+				.filter(|specification| *specification >= demand.specification)
+				.flat_map(<UnusedResources<T>>::iter_key_prefix)
+				.take(T::MaxMatchCandidates::get() as usize)
+				.filter_map(Self::resource)
+				.filter_map(|resource| {
+					let slice = if resource.divisible && !demanded.is_empty() {
+						demanded
+					} else {
+						resource.capacity()
+					};
+					let unit_price = Self::slice_unit_price(&resource, &slice);
+					let rentable = !<FlaggedResources<T>>::contains_key(resource.index) &&
+						resource.account_id != *who &&
+						unit_price <= max_unit_price &&
+						resource.rental_info.end_of_rent >= end_of_rent &&
+						slice.fits_in(&resource.available()) &&
+						demand.is_satisfied_by(&resource.config, &resource.specification) &&
+						!Self::is_reserved(resource.index, now, end_of_rent) &&
+						!<ResourceAuctions<T>>::contains_key(resource.index);
+					rentable.then_some((resource, slice, unit_price))
+				});
+
+		match policy {
+			MatchPolicy::Cheapest => candidates.min_by_key(|(_, _, unit_price)| *unit_price),
+			MatchPolicy::BestReputation =>
+				candidates.max_by_key(|(resource, _, _)| Self::reputation_of(resource).score),
+		}
+	}
+
+	/// reputation of a resource at the current era, counting the rental statistics changed
+	/// since it was last stored
+	pub fn reputation_of(resource: &ResourceOf<T>) -> Reputation {
+		let era = Self::current_era();
+		let mut reputation =
+			Self::resource_reputation(resource.index).unwrap_or_else(|| Reputation::new(era));
+		reputation.decay(T::ReputationDecay::get(), era);
+		reputation.count(&resource.rental_statistics, T::ReputationFaultPenalty::get().into());
+		reputation.update_score();
+		reputation
+	}
+
+	/// store the reputation of resource `index`
+	fn store_reputation(index: u64, reputation: Reputation) {
+		let score = reputation.score;
+		<ResourceReputations<T>>::insert(index, reputation);
+		Self::deposit_event(Event::ReputationUpdated { index, score });
+	}
+
+	/// highest specification whose threshold is reached by a resource with `config` and `scores`,
+	/// `None` if it does not reach the threshold of `General`
+	fn specification_of(
+		config: &ResourceConfigOf<T>,
+		scores: &HardwareScores,
+	) -> Option<Specification> {
+		let reaches = |specification: Specification| {
+			Self::specification_threshold(specification)
+				.map(|threshold| threshold.is_met_by(config, scores))
+		};
+		if reaches(Specification::HighRanking) == Some(true) {
+			Some(Specification::HighRanking)
+		} else if reaches(Specification::Enhanced) == Some(true) {
+			Some(Specification::Enhanced)
+		} else if reaches(Specification::General) != Some(false) {
+			Some(Specification::General)
+		} else {
+			None
+		}
+	}
+
+	/// scores resource `index` is assessed with, the attested ones once its node attested them
+	fn assessed_scores(index: u64) -> HardwareScores {
+		Self::resource_attestation(index)
+			.map(|attestation| attestation.scores)
+			.or_else(|| Self::resource_scores(index))
+			.unwrap_or_default()
+	}
+
+	/// flag resource `index` while its hardware does not reach any specification, returning its
+	/// specification, `General` when flagged
+	fn flag_hardware(index: u64, specification: Option<Specification>) -> Specification {
+		match specification {
+			Some(specification) => {
+				if <FlaggedResources<T>>::take(index).is_some() {
+					Self::deposit_event(Event::ResourceUnflagged { index });
+				}
+				specification
+			},
+			None => {
+				if !<FlaggedResources<T>>::contains_key(index) {
+					let now = <frame_system::Pallet<T>>::block_number();
+					<FlaggedResources<T>>::insert(index, now);
+					Self::deposit_event(Event::ResourceFlagged { index });
+				}
+				Specification::General
+			},
+		}
+	}
+
+	/// check that every dimension of a resource configuration describes something
+	fn ensure_valid_config(config: &ResourceConfigOf<T>) -> DispatchResult {
+		ensure!(config.dimensions.iter().all(|d| d.is_valid()), Error::<T>::InvalidDimension);
+		Ok(())
+	}
+
+	/// get a resource and check that it belongs to `who`
+	fn owned_resource(who: &T::AccountId, index: u64) -> Result<ResourceOf<T>, DispatchError> {
+		let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+		ensure!(resource.account_id == *who, Error::<T>::NotOwner);
+		Ok(resource)
+	}
+
+	/// get an auction still taking bids and check that `who` is not its provider
+	fn open_auction(who: &T::AccountId, auction_index: u64) -> Result<AuctionOf<T>, DispatchError> {
+		let auction = Self::auction(auction_index).ok_or(Error::<T>::AuctionNotFound)?;
+		ensure!(auction.provider != *who, Error::<T>::CannotRentOwnResource);
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(now < auction.close_at, Error::<T>::AuctionClosed);
+		Ok(auction)
+	}
+
+	/// whether `unit_price` reaches the reserve price and beats the highest bid of an auction
+	fn outbids(auction: &AuctionOf<T>, unit_price: u128) -> bool {
+		unit_price >= auction.reserve_price &&
+			!matches!(auction.best_bid, Some((_, best)) if unit_price <= best)
+	}
+
+	/// unreserve the funds held by the bid of `bidder` in an auction
+	fn unreserve_bid(auction_index: u64, bidder: T::AccountId) {
+		if let Some((_, amount)) = <AuctionBids<T>>::take(auction_index, &bidder) {
+			T::Currency::unreserve(&bidder, amount);
+			Self::deposit_event(Event::BidUnreserved { auction_index, bidder, amount });
+		}
+	}
+
+	/// commitment of a sealed bid of `unit_price` by `who`, hiding it behind `salt`
+	pub fn bid_commitment(who: &T::AccountId, unit_price: BalanceOf<T>, salt: &T::Hash) -> T::Hash {
+		T::Hashing::hash_of(&(who, unit_price, salt))
+	}
+
+	/// unit price of a resource in `asset_id`, `None` if it can not be paid in that asset
+	fn asset_unit_price(index: u64, asset_id: &T::AssetId) -> Option<u128> {
+		Self::asset_prices(index)
+			.into_iter()
+			.find(|(id, _)| id == asset_id)
+			.map(|(_, unit_price)| unit_price)
+	}
+
+	/// account escrowing the deposits of the rentals paid in assets
+	pub fn escrow_account() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// take the deposit `amount` of an order from `who`, moving it to the escrow account if
+	/// the order is paid in `asset_id`, reserving it in `Currency` if `None`
+	///
+	/// the escrow account always keeps the minimum balance of an asset besides the deposits, so
+	/// paying a deposit out never reaps it with the deposits of the other orders, the first
+	/// deposit in an asset pays it
+	fn escrow(who: &T::AccountId, asset_id: Option<T::AssetId>, amount: u128) -> DispatchResult {
+		match asset_id {
+			Some(asset_id) => {
+				let mut amount: AssetBalanceOf<T> =
+					amount.try_into().map_err(|_| Error::<T>::PriceOverflow)?;
+				let escrow = Self::escrow_account();
+				// the escrow account is kept alive by the pallet, to hold assets that are not
+				// sufficient
+				if !frame_system::Pallet::<T>::account_exists(&escrow) {
+					frame_system::Pallet::<T>::inc_providers(&escrow);
+				}
+				if T::Assets::balance(asset_id, &escrow).is_zero() {
+					amount = amount.saturating_add(T::Assets::minimum_balance(asset_id));
+				}
+				T::Assets::transfer(asset_id, who, &escrow, amount, false)
+					.map_err(|_| Error::<T>::InsufficientBalance)?;
+			},
+			None => T::Currency::reserve(who, T::NumberToBalance::convert(amount))
+				.map_err(|_| Error::<T>::InsufficientBalance)?,
+		}
+		Ok(())
+	}
+
+	/// pay `amount` of the deposit taken from `renter` to `dest`, out of the escrow account if
+	/// it is in `asset_id`, out of the reserved balance of the renter if `None`
+	///
+	/// an asset that can not be transferred, like an amount below the minimum balance of a new
+	/// account, is kept in the escrow account for `dest` to claim
+	///
+	/// returns the part of `amount` that could not be paid
+	fn pay_deposit(
+		renter: &T::AccountId,
+		dest: &T::AccountId,
+		asset_id: Option<T::AssetId>,
+		amount: u128,
+	) -> Result<u128, DispatchError> {
+		match asset_id {
+			Some(asset_id) => {
+				let unpaid = amount;
+				let amount: AssetBalanceOf<T> =
+					amount.try_into().map_err(|_| Error::<T>::PriceOverflow)?;
+				if let Err(e) =
+					T::Assets::transfer(asset_id, &Self::escrow_account(), dest, amount, true)
+				{
+					log::debug!(
+						target: LOG_TARGET,
+						"payment of {:?} left unclaimed for {:?}: {:?}",
+						amount,
+						dest,
+						e,
+					);
+					<UnclaimedAssets<T>>::mutate(dest, asset_id, |unclaimed| {
+						*unclaimed = unclaimed.saturating_add(unpaid)
+					});
+					Self::deposit_event(Event::AssetsUnclaimed {
+						who: dest.clone(),
+						asset_id,
+						amount,
+					});
+				}
+				Ok(0)
+			},
+			None => {
+				let remaining = T::Currency::repatriate_reserved(
+					renter,
+					dest,
+					T::NumberToBalance::convert(amount),
+					BalanceStatus::Free,
+				)?;
+				Ok(T::BalanceToNumber::convert(remaining))
+			},
+		}
+	}
+
+	/// total price of renting for `duration` blocks at `rent_unit_price`
+	fn rental_price(rent_unit_price: u128, duration: T::BlockNumber) -> Result<u128, Error<T>> {
+		rent_unit_price
+			.checked_mul(duration.saturated_into::<u128>())
+			.ok_or(Error::<T>::PriceOverflow)
+	}
+
+	/// pay the provider for the `used` blocks of an order out of its deposit, refund the rest
+	/// to the renter, release the slice of the resource and remove the order
+	///
+	/// the rental statistics and duration points of the resource count the share of the used
+	/// blocks taken by the slice
+	///
+	/// returns the amounts paid to the provider and refunded to the renter
+	fn do_settle_order(
+		order: RentalOrder<T::BlockNumber, T::AccountId>,
+		used: T::BlockNumber,
+	) -> (BalanceOf<T>, BalanceOf<T>) {
+		let used = used.min(order.rental_info.rent_duration);
+		let payment = order
+			.rental_info
+			.rent_unit_price
+			.saturating_mul(used.saturated_into::<u128>())
+			.min(order.deposit);
+		let mut paid = payment;
+		let mut refund = order.deposit.saturating_sub(payment);
+		let asset_id = Self::order_asset(order.index);
+
+		// what could not be paid to the provider goes back to the renter
+		let unpaid = match Self::pay_deposit(&order.renter, &order.provider, asset_id, paid) {
+			Ok(remaining) => remaining,
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"failed to pay rental order {} to the provider: {:?}",
+					order.index,
+					e,
+				);
+				paid
+			},
+		};
+		paid = paid.saturating_sub(unpaid);
+		refund = refund.saturating_add(unpaid);
+		if let Err(e) = Self::pay_deposit(&order.renter, &order.renter, asset_id, refund) {
+			log::warn!(
+				target: LOG_TARGET,
+				"failed to refund rental order {} to the renter: {:?}",
+				order.index,
+				e,
+			);
+		}
+
+		<Resources<T>>::mutate(order.resource_index, |maybe_resource| {
+			if let Some(resource) = maybe_resource {
+				let leased =
+					order.slice.share_of(&resource.capacity()) * used.saturated_into::<u64>();
+				resource.rental_statistics.add_rental_duration(leased.saturated_into::<u32>());
+				Self::add_provider_points(&order.provider, 0, leased);
+				let allocated_share = resource.allocated_share();
+				resource.allocated = resource.allocated.saturating_sub(order.slice);
+				Self::mutate_class_market(&resource.class(), |market| {
+					market.reallocate(allocated_share, resource.allocated_share());
+				});
+				if resource.status == ResourceStatus::Inuse {
+					resource.update_status(ResourceStatus::Unused);
+					Self::index_unused(resource);
+				}
+				Self::store_reputation(resource.index, Self::reputation_of(resource));
+			}
+		});
+
+		Self::remove_order(&order);
+
+		(T::NumberToBalance::convert(paid), T::NumberToBalance::convert(refund))
+	}
+
+	/// settle an order whose lease has ended, paying the provider the full deposit
+	fn settle_expired_order(order: RentalOrder<T::BlockNumber, T::AccountId>) {
+		let order_index = order.index;
+		let provider = order.provider.clone();
+		let used = order.rental_info.rent_duration;
+		let (amount, _) = Self::do_settle_order(order, used);
+
+		Self::deposit_event(Event::OrderSettled { order_index, provider, amount });
+	}
+
+	/// settle the rental orders queued at `now`, at most `MaxExpiriesPerBlock` of them
+	fn process_expiries(now: T::BlockNumber) -> Weight {
+		let queue = <OrderExpiries<T>>::take(now);
+		let processed = queue.len() as u32;
+
+		for order_index in queue {
+			let order = match Self::rental_order(order_index) {
+				Some(order) => order,
+				// the order has already been settled or terminated
+				None => continue,
+			};
+			// the order has been renewed and is queued again at its new end block
+			if order.rental_info.end_of_rent > now {
+				continue
+			}
+			Self::settle_expired_order(order);
+		}
+
+		T::WeightInfo::process_expiries(processed)
+	}
+
+	/// queue `index` in `Queue` at the first block from `at` that is not full, returning that
+	/// block
+	fn enqueue<Queue, Bound>(at: T::BlockNumber, index: u64) -> Result<T::BlockNumber, Error<T>>
+	where
+		Queue: frame_support::StorageMap<
+			T::BlockNumber,
+			BoundedVec<u64, Bound>,
+			Query = BoundedVec<u64, Bound>,
+		>,
+		Bound: Get<u32>,
+	{
+		let mut block = at;
+		for _ in 0..MAX_QUEUE_PROBES {
+			if Queue::try_mutate(block, |queue| queue.try_push(index)).is_ok() {
+				return Ok(block)
+			}
+			block = block.saturating_add(One::one());
+		}
+		Err(Error::<T>::QueueFull)
+	}
+
+	/// number of blocks without heartbeat after which a resource is marked offline
+	fn heartbeat_timeout() -> T::BlockNumber {
+		T::HeartbeatInterval::get().saturating_mul(T::MaxMissedHeartbeats::get().into())
+	}
+
+	/// record a heartbeat of a resource and move the check of its deadline after it, so only the
+	/// deadlines of resources missing their heartbeats are ever checked
+	fn note_heartbeat(index: u64, now: T::BlockNumber) {
+		let timeout = Self::heartbeat_timeout();
+		if let Some(last) = Self::resource_heartbeat(index) {
+			<HeartbeatDeadlines<T>>::remove(last.saturating_add(timeout), index);
+		}
+		<HeartbeatDeadlines<T>>::insert(now.saturating_add(timeout), index, ());
+		<ResourceHeartbeats<T>>::insert(index, now);
+	}
+
+	/// check that a heartbeat for resource `index` can be accepted from `who` at `now`
+	fn check_heartbeat(
+		index: u64,
+		who: &T::AccountId,
+		now: T::BlockNumber,
+	) -> Result<ResourceOf<T>, Error<T>> {
+		let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+		ensure!(resource.account_id == *who, Error::<T>::NotOwner);
+		if resource.status != ResourceStatus::Offline {
+			let last = Self::resource_heartbeat(index).unwrap_or_else(Zero::zero);
+			ensure!(
+				now >= last.saturating_add(T::HeartbeatInterval::get()),
+				Error::<T>::HeartbeatTooEarly
+			);
+		}
+		Ok(resource)
+	}
+
+	/// check that a hardware attestation for resource `index` can be accepted from `who` at
+	/// `now`
+	fn check_attestation(
+		index: u64,
+		who: &T::AccountId,
+		now: T::BlockNumber,
+	) -> Result<ResourceOf<T>, Error<T>> {
+		let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+		ensure!(resource.account_id == *who, Error::<T>::NotOwner);
+		if let Some(last) = Self::resource_attestation(index) {
+			ensure!(
+				now >= last.block_number.saturating_add(T::AttestationInterval::get()),
+				Error::<T>::AttestationTooEarly
+			);
+		}
+		Ok(resource)
+	}
+
+	/// check that `who` owns resource `index` and answers its pending challenge with `nonce` from
+	/// the node with the peer id of the resource
+	fn check_challenge_response(
+		index: u64,
+		who: &T::AccountId,
+		peer_id: &[u8],
+		nonce: &T::Hash,
+	) -> Result<(), Error<T>> {
+		let resource = Self::resource(index).ok_or(Error::<T>::ResourceNotFound)?;
+		ensure!(resource.account_id == *who, Error::<T>::NotOwner);
+		ensure!(resource.peer_id.as_slice() == peer_id, Error::<T>::PeerIdMismatch);
+		let challenge = Self::audit_challenge(index).ok_or(Error::<T>::ChallengeNotFound)?;
+		ensure!(challenge.nonce == *nonce, Error::<T>::InvalidChallengeResponse);
+		Ok(())
+	}
+
+	/// change the specification of a resource, moving the points of its provider along
+	fn change_specification(index: u64, mut resource: ResourceOf<T>, specification: Specification) {
+		let active = resource.status != ResourceStatus::Offline;
+		if active {
+			Self::sub_provider_points(&resource.account_id, Self::resource_points(&resource), 0);
+		}
+		let old_class = resource.class();
+		<UnusedResources<T>>::remove(&resource.specification, index);
+		resource.specification = specification.clone();
+		Self::index_unused(&resource);
+		Self::change_class(&old_class, &resource.class(), resource.allocated_share());
+		if active {
+			Self::add_provider_points(&resource.account_id, Self::resource_points(&resource), 0);
+		}
+		<Resources<T>>::insert(index, resource);
+
+		Self::deposit_event(Event::ResourceSpecificationChanged { index, specification });
+	}
+
+	/// move the price multiplier of every resource class with its utilization, every
+	/// `PriceAdjustmentPeriod` blocks, adjusting at most `MaxPriceAdjustmentsPerBlock` of them
+	/// per block until all of them are
+	///
+	/// like the fee multiplier of `TargetedFeeAdjustment`, a multiplier `m` becomes
+	/// `m * (1 + v * d + (v * d)^2 / 2)`, `d` being the utilization above the target and `v` the
+	/// variability, `v * d` is subtracted instead when the utilization is below the target
+	fn process_price_adjustment(now: T::BlockNumber) -> Weight {
+		let period = T::PriceAdjustmentPeriod::get();
+		let mut remaining = match <PriceAdjustmentCursor<T>>::get() {
+			Some(last) => <ClassMarkets<T>>::iter_from(<ClassMarkets<T>>::hashed_key_for(last)),
+			None if !now.is_zero() && !period.is_zero() && (now % period).is_zero() =>
+				<ClassMarkets<T>>::iter(),
+			None => return T::DbWeight::get().reads(1),
+		};
+		let markets: Vec<_> = remaining
+			.by_ref()
+			.take(T::MaxPriceAdjustmentsPerBlock::get() as usize)
+			.collect();
+		let classes = markets.len() as u32;
+		match (markets.last(), remaining.next()) {
+			(Some((last, _)), Some(_)) => <PriceAdjustmentCursor<T>>::put(last),
+			_ => <PriceAdjustmentCursor<T>>::kill(),
+		}
+
+		let target = FixedU128::from(T::TargetUtilization::get());
+		let variability = T::PriceAdjustmentVariability::get();
+		let min = T::MinimumPriceMultiplier::get();
+		let max = T::MaximumPriceMultiplier::get();
+		for (class, mut market) in markets {
+			let utilization = market.utilization();
+			let positive = utilization >= target;
+			let diff = if positive { utilization - target } else { target - utilization };
+			let first_term = variability.saturating_mul(diff);
+			let second_term =
+				first_term.saturating_mul(first_term) / FixedU128::saturating_from_integer(2);
+			let previous = market.multiplier;
+			market.multiplier = if positive {
+				let excess = first_term.saturating_add(second_term).saturating_mul(previous);
+				previous.saturating_add(excess)
+			} else {
+				let shortage = first_term.saturating_sub(second_term).saturating_mul(previous);
+				previous.saturating_sub(shortage)
+			}
+			.max(min)
+			.min(max);
+			<ClassMarkets<T>>::insert(class, market);
+		}
+
+		T::WeightInfo::process_price_adjustment(classes)
+	}
+
+	/// mark the resources whose heartbeat deadline passed by `now` as offline, checking at most
+	/// `MaxHeartbeatChecksPerBlock` deadlines and blocks, the rest in the following blocks
+	fn process_heartbeat_deadlines(now: T::BlockNumber) -> Weight {
+		let max = T::MaxHeartbeatChecksPerBlock::get();
+		let mut cursor = Self::heartbeat_cursor();
+		let mut due = Vec::new();
+		let mut checked = 0;
+		while cursor <= now && checked < max {
+			let before = due.len();
+			due.extend(
+				<HeartbeatDeadlines<T>>::drain_prefix(cursor)
+					.map(|(index, _)| index)
+					.take((max - checked) as usize),
+			);
+			let drained = (due.len() - before) as u32;
+			if checked + drained >= max {
+				// the block may still have deadlines to check
+				checked = max;
+			} else {
+				checked += drained.max(1);
+				cursor = cursor.saturating_add(One::one());
+			}
+		}
+		<HeartbeatCursor<T>>::put(cursor);
+
+		for index in due {
+			let mut resource = match Self::resource(index) {
+				Some(resource) if resource.status != ResourceStatus::Offline => resource,
+				// the resource has been removed or is already offline
+				_ => continue,
+			};
+			let last = Self::resource_heartbeat(index).unwrap_or_else(Zero::zero);
+			// a newer heartbeat queued a later deadline
+			if last.saturating_add(Self::heartbeat_timeout()) > now {
+				continue
+			}
+
+			resource.rental_statistics.add_fault_count();
+			resource
+				.rental_statistics
+				.add_fault_duration(now.saturating_sub(last).saturated_into::<u32>());
+			let leased = <ResourceLeases<T>>::contains_key(index);
+			resource.update_status(ResourceStatus::Offline);
+			Self::index_unused(&resource);
+			let provider = resource.account_id.clone();
+			let capacity = resource.capacity();
+			Self::sub_provider_points(&provider, Self::resource_points(&resource), 0);
+			<Resources<T>>::insert(index, resource);
+			// the downtime is accounted up to now, the rest when the resource comes back
+			<ResourceHeartbeats<T>>::insert(index, now);
+
+			Self::deposit_event(Event::ResourceOffline { index });
+
+			if leased {
+				Self::slash_offline_resource(index, &provider, &capacity);
+			}
+		}
+
+		T::WeightInfo::process_heartbeat_deadlines(checked)
+	}
+
+	/// challenge a random sample of the `Unused` and `Inuse` resources at the start of every
+	/// audit period, at most `AuditSampleSize` of them
+	fn process_audit(now: T::BlockNumber) -> Weight {
+		let period = T::AuditPeriod::get();
+		if now.is_zero() || period.is_zero() || !(now % period).is_zero() {
+			return 0
+		}
+
+		let resource_index = Self::resource_index();
+		if resource_index == 0 {
+			return T::WeightInfo::process_audit()
+		}
+		let (seed, _) = T::Randomness::random(AUDIT_RANDOMNESS_SUBJECT);
+		let deadline = now.saturating_add(T::ChallengeDeadline::get());
+		let sample_size = T::AuditSampleSize::get();
+		let mut challenged = BoundedVec::<u64, T::AuditSampleSize>::default();
+
+		for probe in 0..sample_size.saturating_mul(AUDIT_PROBES_PER_SAMPLE) {
+			if challenged.len() as u32 >= sample_size {
+				break
+			}
+			let random = (seed, probe).using_encoded(T::Hashing::hash);
+			let index = u64::decode(&mut TrailingZeroInput::new(random.as_ref()))
+				.expect("input is padded with zeroes; qed") %
+				resource_index;
+			let eligible = Self::resource(index).is_some_and(|resource| {
+				matches!(resource.status, ResourceStatus::Unused | ResourceStatus::Inuse)
+			});
+			if !eligible || <AuditChallenges<T>>::contains_key(index) {
+				continue
+			}
+
+			let nonce = (seed, index).using_encoded(T::Hashing::hash);
+			if challenged.try_push(index).is_err() {
+				break
+			}
+			<AuditChallenges<T>>::insert(index, AuditChallenge { nonce, deadline });
+			Self::deposit_event(Event::ResourceChallenged { index, deadline });
+		}
+
+		if !challenged.is_empty() {
+			<ChallengeDeadlines<T>>::insert(deadline, challenged);
+		}
+
+		T::WeightInfo::process_audit()
+	}
+
+	/// count the challenges whose deadline is `now` and that are still pending as faults of
+	/// their resources
+	fn process_challenge_deadlines(now: T::BlockNumber) -> Weight {
+		let due = <ChallengeDeadlines<T>>::take(now);
+		let checked = due.len() as u32;
+
+		for index in due {
+			match Self::audit_challenge(index) {
+				Some(challenge) if challenge.deadline == now => <AuditChallenges<T>>::remove(index),
+				// the challenge has been answered
+				_ => continue,
+			}
+			<Resources<T>>::mutate(index, |maybe_resource| {
+				if let Some(resource) = maybe_resource {
+					resource.rental_statistics.add_fault_count();
+				}
+			});
+
+			Self::deposit_event(Event::ChallengeFailed { index });
+		}
+
+		T::WeightInfo::process_challenge_deadlines(checked)
+	}
+
+	/// weight of a specification in the bond and points of a resource
+	fn specification_multiplier(specification: &Specification) -> u32 {
+		match specification {
+			Specification::General => 1,
+			Specification::Enhanced => 2,
+			Specification::HighRanking => 4,
+		}
+	}
+
+	/// bond required for a resource, scaled by every dimension of its configuration and its
+	/// specification
+	fn required_bond(resource: &ResourceOf<T>) -> BalanceOf<T> {
+		let multiplier = Self::specification_multiplier(&resource.specification);
+		let config = &resource.config;
+		let units = |amount: u64| T::NumberToBalance::convert(amount.into());
+		T::BondPerCpu::get()
+			.saturating_mul(units(config.cpu))
+			.saturating_add(T::BondPerMemory::get().saturating_mul(units(config.memory)))
+			.saturating_add(
+				T::BondPerStorage::get().saturating_mul(units(config.storage(DiskKind::Hdd))),
+			)
+			.saturating_add(T::BondPerBandwidth::get().saturating_mul(units(config.bandwidth())))
+			.saturating_add(
+				T::BondPerAccelerator::get()
+					.saturating_mul(units(config.accelerators(None).into())),
+			)
+			.saturating_mul(multiplier.into())
+	}
+
+	/// points earned by a provider for keeping a resource online, scaled by its size and
+	/// specification
+	fn resource_points(resource: &ResourceOf<T>) -> u64 {
+		resource
+			.config
+			.cpu
+			.saturating_add(resource.config.memory)
+			.saturating_mul(Self::specification_multiplier(&resource.specification).into())
+	}
+
+	/// add resource and duration points to a provider
+	fn add_provider_points(who: &T::AccountId, resource_points: u64, duration_points: u64) {
+		if resource_points == 0 && duration_points == 0 {
+			return
+		}
+		<Points<T>>::mutate(who, |maybe_points| {
+			let points = maybe_points.get_or_insert_with(|| ProviderPoints::new(0, 0, 0));
+			points.add_points(resource_points, duration_points);
+			Self::checkpoint_points(who, points.total_points);
+		});
+		<TotalResourcePoints<T>>::mutate(|total| {
+			*total = total.saturating_add(resource_points.into())
+		});
+		<TotalDurationPoints<T>>::mutate(|total| {
+			*total = total.saturating_add(duration_points.into())
+		});
+	}
+
+	/// remove resource and duration points from a provider
+	fn sub_provider_points(who: &T::AccountId, resource_points: u64, duration_points: u64) {
+		<Points<T>>::mutate_exists(who, |maybe_points| {
+			if let Some(points) = maybe_points {
+				// keep the totals in line with what is actually removed
+				let resource_points = resource_points.min(points.resource_points);
+				let duration_points = duration_points.min(points.duration_points);
+				points.sub_points(resource_points, duration_points);
+				Self::checkpoint_points(who, points.total_points);
+				<TotalResourcePoints<T>>::mutate(|total| {
+					*total = total.saturating_sub(resource_points.into())
+				});
+				<TotalDurationPoints<T>>::mutate(|total| {
+					*total = total.saturating_sub(duration_points.into())
+				});
+				if points.total_points == 0 {
+					*maybe_points = None;
+				}
+			}
+		});
+	}
+
+	/// move the bond of a resource to the unbonding queue of its provider
+	///
+	/// returns false if the resource has no bond
+	fn do_unbond(who: &T::AccountId, index: u64) -> Result<bool, Error<T>> {
+		let amount = match Self::resource_bond(index) {
+			Some(amount) => amount,
+			None => return Ok(false),
+		};
+		let unlock_at =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::UnbondingPeriod::get());
+		<Unbonding<T>>::try_append(who, (unlock_at, amount))
+			.map_err(|_| Error::<T>::TooManyUnbondingChunks)?;
+		<ResourceBonds<T>>::remove(index);
+
+		Self::deposit_event(Event::ResourceUnbonded { index, amount, unlock_at });
+		Ok(true)
+	}
+
+	/// slash part of the bond of a resource with `capacity` that went offline during its
+	/// leases and pay it to the renters, each one getting the share of its slice, scaled down
+	/// when the slices overlap in cpu or memory so the shares never add up to more than the slash
+	///
+	/// the leases paid in an asset get their share of the bond too, and are also slashed in that
+	/// asset, the `OfflineSlashRatio` of their own deposit, so they are slashed whether the
+	/// resource is bonded or not
+	fn slash_offline_resource(index: u64, provider: &T::AccountId, capacity: &ResourceSlice) {
+		let orders: Vec<_> = Self::resource_leases(index)
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(Self::rental_order)
+			.collect();
+		for order in &orders {
+			if let Some(asset_id) = Self::order_asset(order.index) {
+				Self::slash_asset_order(index, order.clone(), asset_id);
+			}
+		}
+
+		let mut bonded = match Self::resource_bond(index) {
+			Some(bonded) => bonded,
+			None => return,
+		};
+		let slash = T::OfflineSlashRatio::get() * bonded;
+		// a slice takes the largest of its cpu and memory shares, so slices using more cpus than
+		// memory and others the other way round take more than the whole resource together
+		let total: u64 = orders
+			.iter()
+			.map(|order| order.slice.share_of(capacity).deconstruct() as u64)
+			.sum();
+		let accuracy = Perbill::ACCURACY as u64;
+		let scale = Perbill::from_rational(accuracy, total.max(accuracy));
+		for order in orders {
+			let share = scale.mul_floor(order.slice.share_of(capacity).mul_floor(slash));
+			// the part that could not be moved is still reserved by the provider
+			let remaining = match T::Currency::repatriate_reserved(
+				provider,
+				&order.renter,
+				share,
+				BalanceStatus::Free,
+			) {
+				Ok(remaining) => remaining,
+				Err(e) => {
+					log::warn!(
+						target: LOG_TARGET,
+						"failed to pay the slash of resource {} to the renter of order {}: {:?}",
+						index,
+						order.index,
+						e,
+					);
+					continue
+				},
+			};
+			let amount = share.saturating_sub(remaining);
+			bonded = bonded.saturating_sub(amount);
+
+			Self::deposit_event(Event::BondSlashed { index, renter: order.renter, amount });
+		}
+		<ResourceBonds<T>>::insert(index, bonded);
+	}
+
+	/// refund the `OfflineSlashRatio` part of the deposit of an order paid in `asset_id` to its
+	/// renter, the provider being paid that much less when the order is settled
+	fn slash_asset_order(
+		index: u64,
+		mut order: RentalOrder<T::BlockNumber, T::AccountId>,
+		asset_id: T::AssetId,
+	) {
+		let amount = T::OfflineSlashRatio::get() * order.deposit;
+		if let Err(e) = Self::pay_deposit(&order.renter, &order.renter, Some(asset_id), amount) {
+			log::warn!(
+				target: LOG_TARGET,
+				"failed to pay the slash of resource {} to the renter of order {}: {:?}",
+				index,
+				order.index,
+				e,
+			);
+			return
+		}
+		order.deposit = order.deposit.saturating_sub(amount);
+
+		Self::deposit_event(Event::AssetSlashed {
+			index,
+			renter: order.renter.clone(),
+			asset_id,
+			amount: amount.saturated_into(),
+		});
+		<RentalOrders<T>>::insert(order.index, order);
+	}
+
+	/// record the points of a provider at the end of the current era, forgetting the
+	/// checkpoints no claimable era needs anymore
+	fn checkpoint_points(who: &T::AccountId, total_points: u128) {
+		let era = Self::current_era();
+		let oldest = era.saturating_sub(T::RewardHistoryDepth::get());
+		<PointsHistory<T>>::mutate_exists(who, |maybe_history| {
+			let mut checkpoints = maybe_history.take().unwrap_or_default().into_inner();
+			let hidden = checkpoints.iter().skip(1).take_while(|(e, _)| *e <= oldest).count();
+			checkpoints.drain(..hidden);
+			match checkpoints.last_mut() {
+				Some((last, points)) if *last == era => *points = total_points,
+				_ => checkpoints.push((era, total_points)),
+			}
+			// only left after the history depth has been lowered
+			let excess = checkpoints.len().saturating_sub(HistoryLength::<T>::get() as usize);
+			checkpoints.drain(..excess);
+			if checkpoints.iter().any(|(_, points)| !points.is_zero()) {
+				*maybe_history = Some(checkpoints.try_into().expect("drained to the bound; qed"));
+			}
+		});
+	}
+
+	/// points of a provider at the end of `era`
+	fn points_at(who: &T::AccountId, era: EraIndex) -> u128 {
+		Self::points_history(who)
+			.iter()
+			.rev()
+			.find(|(checkpoint, _)| *checkpoint <= era)
+			.map_or(0, |(_, points)| *points)
+	}
+
+	/// at the end of an era, record the total points of the providers and the reward pot, each
+	/// provider claiming its share from its own points checkpoints, and forget the era that can
+	/// no longer be claimed
+	fn process_era(now: T::BlockNumber) -> Weight {
+		let era_duration = T::EraDuration::get();
+		if now.is_zero() || era_duration.is_zero() || !(now % era_duration).is_zero() {
+			return 0
+		}
+
+		let era = Self::current_era();
+		let total_points =
+			Self::total_resource_points().saturating_add(Self::total_duration_points());
+		let reward_pot = T::EraRewardPot::get();
+
+		if !total_points.is_zero() {
+			<ErasTotalPoints<T>>::insert(era, total_points);
+			<ErasRewardPot<T>>::insert(era, reward_pot);
+		}
+		<CurrentEra<T>>::put(era.saturating_add(1));
+
+		if let Some(expired) = era.checked_sub(T::RewardHistoryDepth::get()) {
+			<ErasRewardPot<T>>::remove(expired);
+			<ErasTotalPoints<T>>::remove(expired);
+		}
+
+		Self::deposit_event(Event::EraEnded { era, reward_pot, total_points });
+
+		T::WeightInfo::process_era()
+	}
+
+	/// send heartbeats for the resources of the provider keys in the local keystore
+	fn send_heartbeats(now: T::BlockNumber) -> Result<(), &'static str> {
+		let local_keys =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all();
+		if local_keys.is_empty() {
+			return Err("no local provider keys")
+		}
+
+		for key in local_keys {
+			let generic_public =
+				<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+			let public: T::Public = generic_public.into();
+			let who = public.clone().into_account();
+
+			for index in Self::provider(&who).unwrap_or_default() {
+				if Self::check_heartbeat(index, &who, now).is_err() {
+					continue
+				}
+				if let Err(e) = Self::send_single_heartbeat(index, public.clone(), now) {
+					log::debug!(
+						target: LOG_TARGET,
+						"skipping heartbeat of resource {} at {:?}: {}",
+						index,
+						now,
+						e,
+					);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// lock the submission of a transaction for resource `index` at `now` under `prefix`, unless
+	/// the one submitted less than `interval` blocks ago may still be waiting for inclusion,
+	/// returns the key of the lock
+	fn lock_submission(
+		prefix: &[u8],
+		index: u64,
+		now: T::BlockNumber,
+		interval: T::BlockNumber,
+	) -> Result<Vec<u8>, &'static str> {
+		let key = {
+			let mut key = prefix.to_vec();
+			key.extend(index.encode());
+			key
+		};
+		let storage = StorageValueRef::persistent(&key);
+		let res =
+			storage.mutate(|sent_at: Result<Option<T::BlockNumber>, StorageRetrievalError>| {
+				match sent_at {
+					// we are still waiting for the previous transaction to be included
+					Ok(Some(sent_at)) if sent_at.saturating_add(interval) > now =>
+						Err("waiting for inclusion"),
+					_ => Ok(now),
+				}
+			});
+		match res {
+			Ok(_) => Ok(key),
+			Err(MutateStorageError::ValueFunctionFailed(e)) => Err(e),
+			Err(MutateStorageError::ConcurrentModification(_)) => Err("failed to acquire lock"),
+		}
+	}
+
+	/// sign and submit a heartbeat, unless one is already waiting for inclusion
+	fn send_single_heartbeat(
+		index: u64,
+		public: T::Public,
+		now: T::BlockNumber,
+	) -> Result<(), &'static str> {
+		let lock =
+			Self::lock_submission(HEARTBEAT_LOCK_PREFIX, index, now, T::HeartbeatInterval::get())?;
+
+		let result = Signer::<T, T::AuthorityId>::any_account()
+			.with_filter(sp_std::vec![public])
+			.send_unsigned_transaction(
+				|account| HeartbeatPayload {
+					block_number: now,
+					resource_index: index,
+					public: account.public.clone(),
+				},
+				|heartbeat, signature| Call::heartbeat { heartbeat, signature },
+			)
+			.ok_or("no local account for the provider key");
+
+		if !matches!(result, Ok((_, Ok(())))) {
+			// allow to retry in the next block
+			StorageValueRef::persistent(&lock).clear();
+			return Err("failed to submit heartbeat")
+		}
+
+		Ok(())
+	}
+
+	/// attest the hardware benchmark scores last measured by the node for the resources of the
+	/// provider keys in the local keystore that are registered with the peer id of the node
+	fn send_attestations(now: T::BlockNumber) -> Result<(), &'static str> {
+		let scores = StorageValueRef::persistent(HWBENCH_STORAGE_KEY)
+			.get::<HardwareScores>()
+			.map_err(|_| "undecodable local hardware benchmark")?
+			.ok_or("no local hardware benchmark")?;
+		let peer_id = StorageValueRef::persistent(PEER_ID_STORAGE_KEY)
+			.get::<Vec<u8>>()
+			.map_err(|_| "undecodable local peer id")?
+			.ok_or("no local peer id")?;
+		let local_keys =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all();
+		if local_keys.is_empty() {
+			return Err("no local provider keys")
+		}
+
+		for key in local_keys {
+			let generic_public =
+				<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+			let public: T::Public = generic_public.into();
+			let who = public.clone().into_account();
+
+			for index in Self::provider(&who).unwrap_or_default() {
+				// the other resources of the provider run on other nodes
+				if !matches!(Self::resource(index), Some(r) if r.peer_id.as_slice() == peer_id) {
+					continue
+				}
+				if Self::check_attestation(index, &who, now).is_err() {
+					continue
+				}
+				if let Err(e) =
+					Self::send_single_attestation(index, public.clone(), scores.clone(), now)
+				{
+					log::debug!(
+						target: LOG_TARGET,
+						"skipping attestation of resource {} at {:?}: {}",
+						index,
+						now,
+						e,
+					);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// sign and submit a hardware attestation, unless one is already waiting for inclusion
+	fn send_single_attestation(
+		index: u64,
+		public: T::Public,
+		scores: HardwareScores,
+		now: T::BlockNumber,
+	) -> Result<(), &'static str> {
+		let lock = Self::lock_submission(
+			ATTESTATION_LOCK_PREFIX,
+			index,
+			now,
+			T::AttestationInterval::get(),
+		)?;
+
+		let result = Signer::<T, T::AuthorityId>::any_account()
+			.with_filter(sp_std::vec![public])
+			.send_unsigned_transaction(
+				|account| AttestationPayload {
+					block_number: now,
+					resource_index: index,
+					scores: scores.clone(),
+					public: account.public.clone(),
+				},
+				|attestation, signature| Call::attest_hardware { attestation, signature },
+			)
+			.ok_or("no local account for the provider key");
+
+		if !matches!(result, Ok((_, Ok(())))) {
+			// allow to retry in the next block
+			StorageValueRef::persistent(&lock).clear();
+			return Err("failed to submit attestation")
+		}
+
+		Ok(())
+	}
+
+	/// answer the pending audit challenges of the resources of the provider keys in the local
+	/// keystore that are registered with the peer id of the node
+	fn send_challenge_responses(now: T::BlockNumber) -> Result<(), &'static str> {
+		let peer_id = StorageValueRef::persistent(PEER_ID_STORAGE_KEY)
+			.get::<Vec<u8>>()
+			.map_err(|_| "undecodable local peer id")?
+			.ok_or("no local peer id")?;
+		let local_keys =
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all();
+		if local_keys.is_empty() {
+			return Err("no local provider keys")
+		}
+
+		for key in local_keys {
+			let generic_public =
+				<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+			let public: T::Public = generic_public.into();
+			let who = public.clone().into_account();
+
+			for index in Self::provider(&who).unwrap_or_default() {
+				// the other resources of the provider run on other nodes
+				if !matches!(Self::resource(index), Some(r) if r.peer_id.as_slice() == peer_id) {
+					continue
+				}
+				let challenge = match Self::audit_challenge(index) {
+					Some(challenge) => challenge,
+					None => continue,
+				};
+				if let Err(e) = Self::send_single_challenge_response(
+					index,
+					public.clone(),
+					&peer_id,
+					challenge,
+					now,
+				) {
+					log::debug!(
+						target: LOG_TARGET,
+						"skipping challenge response of resource {} at {:?}: {}",
+						index,
+						now,
+						e,
+					);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// sign and submit the response to an audit challenge, unless one is already waiting for
+	/// inclusion
+	fn send_single_challenge_response(
+		index: u64,
+		public: T::Public,
+		peer_id: &[u8],
+		challenge: AuditChallenge<T::BlockNumber, T::Hash>,
+		now: T::BlockNumber,
+	) -> Result<(), &'static str> {
+		let lock =
+			Self::lock_submission(CHALLENGE_LOCK_PREFIX, index, now, T::ChallengeDeadline::get())?;
+
+		let result = Signer::<T, T::AuthorityId>::any_account()
+			.with_filter(sp_std::vec![public])
+			.send_unsigned_transaction(
+				|account| ChallengeResponsePayload {
+					resource_index: index,
+					nonce: challenge.nonce,
+					peer_id: peer_id.to_vec(),
+					public: account.public.clone(),
+				},
+				|response, signature| Call::answer_challenge { response, signature },
+			)
+			.ok_or("no local account for the provider key");
+
+		if !matches!(result, Ok((_, Ok(())))) {
+			// allow to retry in the next block
+			StorageValueRef::persistent(&lock).clear();
+			return Err("failed to submit challenge response")
+		}
+
+		Ok(())
+	}
+
+	/// change the counters of the market of a resource class, removing it once it has no
+	/// resources
+	fn mutate_class_market(class: &ResourceClass, f: impl FnOnce(&mut ClassMarket)) {
+		<ClassMarkets<T>>::mutate_exists(class, |maybe_market| {
+			let market = maybe_market.get_or_insert_with(ClassMarket::default);
+			f(market);
+			if market.resources == 0 {
+				*maybe_market = None;
+			}
+		});
+	}
+
+	/// move a resource with an `allocated` share from the market of its old class to the one of
+	/// its new class
+	fn change_class(old: &ResourceClass, new: &ResourceClass, allocated: Perbill) {
+		if old == new {
+			return
+		}
+		Self::mutate_class_market(old, |market| {
+			market.resources = market.resources.saturating_sub(1);
+			market.reallocate(allocated, Perbill::zero());
+		});
+		Self::mutate_class_market(new, |market| {
+			market.resources = market.resources.saturating_add(1);
+			market.reallocate(Perbill::zero(), allocated);
+		});
+	}
+
+	/// remove a reservation and the blocks it books
+	fn remove_reservation(reservation: &ReservationOf<T>) {
+		<Reservations<T>>::remove(reservation.index);
+		<ResourceReservations<T>>::mutate_exists(reservation.resource_index, |maybe_windows| {
+			if let Some(windows) = maybe_windows {
+				windows.retain(|(start, _)| *start != reservation.start);
+				if windows.is_empty() {
+					*maybe_windows = None;
+				}
+			}
+		});
+	}
+
+	/// remove an order and every association pointing to it
+	fn remove_order(order: &RentalOrder<T::BlockNumber, T::AccountId>) {
+		<RentalOrders<T>>::remove(order.index);
+		<OrderRatings<T>>::remove(order.index);
+		<OrderAssets<T>>::remove(order.index);
+		<ResourceLeases<T>>::mutate_exists(order.resource_index, |maybe_indexes| {
+			if let Some(indexes) = maybe_indexes {
+				indexes.retain(|i| *i != order.index);
+				if indexes.is_empty() {
+					*maybe_indexes = None;
+				}
+			}
+		});
+		<RenterOrders<T>>::mutate_exists(&order.renter, |maybe_indexes| {
+			if let Some(indexes) = maybe_indexes {
+				indexes.retain(|i| *i != order.index);
+				if indexes.is_empty() {
+					*maybe_indexes = None;
+				}
+			}
+		});
+	}
+
+	/// resources matching every given filter, used by the runtime api
+	pub fn resources_by(
+		status: Option<ResourceStatus>,
+		specification: Option<Specification>,
+		owner: Option<T::AccountId>,
+	) -> Vec<ResourceOf<T>> {
+		<Resources<T>>::iter_values()
+			.filter(|resource| {
+				status.is_none_or(|status| resource.status == status) &&
+					specification.as_ref().is_none_or(|spec| resource.specification == *spec) &&
+					owner.as_ref().is_none_or(|owner| resource.account_id == *owner)
+			})
+			.collect()
+	}
+
+	/// resources with `status`, if given, satisfying every requirement of `demand`, used by the
+	/// runtime api
+	pub fn resources_matching(
+		demand: &ResourceDemandOf<T>,
+		status: Option<ResourceStatus>,
+	) -> Vec<ResourceOf<T>> {
+		<Resources<T>>::iter_values()
+			.filter(|resource| {
+				status.is_none_or(|status| resource.status == status) &&
+					demand.is_satisfied_by(&resource.config, &resource.specification)
+			})
+			.collect()
+	}
+
+	/// resources registered by `who`, used by the runtime api
+	pub fn provider_resources(who: &T::AccountId) -> Vec<ResourceOf<T>> {
+		Self::provider(who)
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(Self::resource)
+			.collect()
+	}
+
+	/// current reputation of resource `index`, used by the runtime api
+	pub fn current_reputation(index: u64) -> Option<Reputation> {
+		Self::resource(index).map(|resource| Self::reputation_of(&resource))
+	}
+
+	/// mean score of the resources of `who`, `None` if it has none, used by the runtime api
+	pub fn provider_reputation(who: &T::AccountId) -> Option<Perbill> {
+		let scores = Self::provider_resources(who)
+			.iter()
+			.map(|resource| u64::from(Self::reputation_of(resource).score.deconstruct()))
+			.collect::<Vec<_>>();
+		if scores.is_empty() {
+			return None
+		}
+		let mean = scores.iter().sum::<u64>() / scores.len() as u64;
+		Some(Perbill::from_parts(mean.saturated_into()))
+	}
+
+	/// resources whose score reaches `min_score` with their reputation, the best first, used by
+	/// the runtime api
+	pub fn resources_by_reputation(min_score: Perbill) -> Vec<(ResourceOf<T>, Reputation)> {
+		let mut resources = <Resources<T>>::iter_values()
+			.map(|resource| {
+				let reputation = Self::reputation_of(&resource);
+				(resource, reputation)
+			})
+			.filter(|(_, reputation)| reputation.score >= min_score)
+			.collect::<Vec<_>>();
+		resources.sort_by(|(a, ra), (b, rb)| rb.score.cmp(&ra.score).then(a.index.cmp(&b.index)));
+		resources
+	}
+
+	/// price of renting resource `index` for `duration` blocks, `None` if the resource does not
+	/// exist or the price overflows
+	pub fn rental_quote(index: u64, duration: T::BlockNumber) -> Option<BalanceOf<T>> {
+		let resource = Self::resource(index)?;
+		Self::rental_price(Self::unit_price_of(&resource), duration)
+			.ok()
+			.map(T::NumberToBalance::convert)
+	}
+
+	/// current unit price of resource `index`, `None` if the resource does not exist
+	pub fn unit_price_quote(index: u64) -> Option<BalanceOf<T>> {
+		Self::resource(index)
+			.map(|resource| T::NumberToBalance::convert(Self::unit_price_of(&resource)))
+	}
+}
+
+impl<T: Config> ProviderInterface<T::AccountId> for Pallet<T> {
+	/// points of every provider, with the total resource and duration points
+	fn get_providers_points() -> (Vec<(T::AccountId, ProviderPoints)>, u128, u128) {
+		(
+			<Points<T>>::iter().collect(),
+			Self::total_resource_points(),
+			Self::total_duration_points(),
+		)
+	}
+}
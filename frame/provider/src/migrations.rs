@@ -0,0 +1,173 @@
+//! The migrations of this pallet.
+//!
+//! Every migration upgrades the storage from one [`StorageVersion`] to the next and does nothing
+//! on any other version, so a runtime can keep all of them in its `Executive` migrations, oldest
+//! first, and only the ones the chain has not run yet take effect.
+
+use super::*;
+use frame_support::{
+	storage::migration,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess},
+	Twox64Concat,
+};
+
+/// encodings of the resource types before their byte strings were bounded
+mod v0 {
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct ResourceConfig {
+		pub cpu: u64,
+		pub memory: u64,
+		pub system: Vec<u8>,
+		pub cpu_model: Vec<u8>,
+	}
+
+	#[derive(Decode)]
+	pub struct ComputingResource<BlockNumber, AccountId> {
+		pub _index: u64,
+		pub account_id: AccountId,
+		pub peer_id: Vec<u8>,
+		pub config: ResourceConfig,
+		pub rental_statistics: ResourceRentalStatistics,
+		pub rental_info: ResourceRentalInfo<BlockNumber>,
+		pub status: ResourceStatus,
+		pub public_ip: Vec<u8>,
+		pub specification: Specification,
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	type OldResourceOf<T> = v0::ComputingResource<
+		<T as frame_system::Config>::BlockNumber,
+		<T as frame_system::Config>::AccountId,
+	>;
+
+	/// Rebuilds the storage of the first version of the pallet, which only stored the resources,
+	/// their providers and the next resource index.
+	///
+	/// Every resource gets bounded byte strings and is registered at the current block. It is
+	/// stored with the entries indexing it like a resource of the genesis: its peer id, its
+	/// provider, its class market, a heartbeat at the current block and its points. No order
+	/// leases it, so a resource in use or locked becomes unused. Resources with a byte string
+	/// over its bound, a peer id registered by a resource with a lower index, or past the bound
+	/// of the resource list of their provider are removed.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"MigrateToV1 skipped, storage is at version {:?}",
+					on_chain
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut resources: Vec<(u64, OldResourceOf<T>)> =
+				migration::storage_key_iter::<_, _, Twox64Concat>(pallet, b"Resources")
+					.drain()
+					.collect();
+			resources.sort_by_key(|(index, _)| *index);
+			// the providers list their resources again as they are stored
+			let providers = migration::storage_key_iter::<T::AccountId, Vec<u64>, Twox64Concat>(
+				pallet,
+				b"Providers",
+			)
+			.drain()
+			.count() as Weight;
+
+			let mut migrated: Weight = 0;
+			let mut next_index = Pallet::<T>::resource_index();
+			for (index, old) in resources {
+				next_index = next_index.max(index.saturating_add(1));
+				let config = match (old.config.system.try_into(), old.config.cpu_model.try_into()) {
+					(Ok(system), Ok(cpu_model)) => ResourceConfig::new(
+						old.config.cpu,
+						old.config.memory,
+						system,
+						cpu_model,
+						Default::default(),
+					),
+					_ => {
+						log::warn!(target: LOG_TARGET, "removed oversized resource {}", index);
+						continue
+					},
+				};
+				let (peer_id, public_ip) = match (old.peer_id.try_into(), old.public_ip.try_into())
+				{
+					(Ok(peer_id), Ok(public_ip)) => (peer_id, public_ip),
+					_ => {
+						log::warn!(target: LOG_TARGET, "removed oversized resource {}", index);
+						continue
+					},
+				};
+				let status = match old.status {
+					ResourceStatus::Offline => ResourceStatus::Offline,
+					_ => ResourceStatus::Unused,
+				};
+				let resource = ComputingResource::new(
+					index,
+					old.account_id,
+					peer_id,
+					config,
+					old.rental_statistics,
+					old.rental_info,
+					status,
+					public_ip,
+					old.specification,
+					now,
+				);
+				if let Err(e) = Pallet::<T>::insert_resource(index, &resource, now) {
+					log::warn!(target: LOG_TARGET, "removed resource {}: {:?}", index, e);
+					continue
+				}
+				migrated += 1;
+			}
+			<ResourceIndex<T>>::put(next_index);
+			<HeartbeatCursor<T>>::put(now);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "migrated {} resources", migrated);
+
+			// a resource reads and writes its peer id, provider, class market, heartbeat, points
+			// and unused index
+			T::DbWeight::get()
+				.reads_writes(migrated * 8 + providers + 2, migrated * 9 + providers + 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::ensure;
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"storage version was not set by the migration"
+			);
+			for (index, resource) in <Resources<T>>::iter() {
+				ensure!(
+					Pallet::<T>::peer_id_resource(&resource.peer_id) == Some(index),
+					"peer id of a resource is not registered after the migration"
+				);
+				ensure!(
+					Pallet::<T>::provider(&resource.account_id)
+						.unwrap_or_default()
+						.contains(&index),
+					"resource is not listed by its provider after the migration"
+				);
+				ensure!(
+					Pallet::<T>::resource_heartbeat(index).is_some(),
+					"resource has no heartbeat after the migration"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
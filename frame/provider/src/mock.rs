@@ -0,0 +1,382 @@
+//! Mock runtime for pallet-provider tests.
+
+use super::*;
+use crate as pallet_provider;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly, Hooks, Randomness, UnixTime},
+};
+use sp_core::{sr25519, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, ConvertInto, Extrinsic as ExtrinsicT, Hash, IdentityLookup, Verify},
+	AccountId32, BuildStorage, MultiSignature, MultiSigner,
+};
+use std::{sync::Arc, time::Duration};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type AssetBalance = u64;
+pub type BlockNumber = u64;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Extrinsic = TestXt<Call, ()>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Provider: pallet_provider::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = ConstU64<{ u64::MAX }>;
+	type ScheduleOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<2>;
+	type WeightInfo = ();
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PreimageProvider = ();
+	type NoPreimagePostponement = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = AssetBalance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<32>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = MultiSigner;
+	type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: <MultiSignature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub static RewardPotAccount: Option<AccountId> = None;
+	/// unix time in milliseconds
+	pub static Now: u64 = 0;
+	/// far after the blocks tests run to, unless a test sets a shorter period
+	pub static AuditPeriod: BlockNumber = 1_000;
+}
+
+pub struct MockTime;
+
+impl UnixTime for MockTime {
+	fn now() -> Duration {
+		Duration::from_millis(Now::get())
+	}
+}
+
+/// randomness changing with the block number
+pub struct TestRandomness;
+
+impl Randomness<H256, BlockNumber> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, BlockNumber) {
+		(BlakeTwo256::hash_of(&(subject, System::block_number())), System::block_number())
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BalanceToNumber = ConvertInto;
+	type NumberToBalance = ConvertInto;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = ProviderPalletId;
+	type MaxExpiriesPerBlock = ConstU32<4>;
+	type AuthorityId = crypto::ProviderAuthId;
+	type HeartbeatInterval = ConstU64<5>;
+	type MaxMissedHeartbeats = ConstU32<2>;
+	type MaxHeartbeatChecksPerBlock = ConstU32<4>;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type AttestationInterval = ConstU64<10>;
+	type UnixTime = MockTime;
+	type Randomness = TestRandomness;
+	type Call = Call;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type AuditPeriod = AuditPeriod;
+	type AuditSampleSize = ConstU32<2>;
+	type ChallengeDeadline = ConstU64<3>;
+	type PriceAdjustmentPeriod = ConstU64<2>;
+	type MaxPriceAdjustmentsPerBlock = ConstU32<1>;
+	type TargetUtilization = TargetUtilization;
+	type PriceAdjustmentVariability = PriceAdjustmentVariability;
+	type MinimumPriceMultiplier = MinimumPriceMultiplier;
+	type MaximumPriceMultiplier = MaximumPriceMultiplier;
+	type ReputationDecay = ReputationDecay;
+	type ReputationFaultPenalty = ConstU32<5>;
+	type BondPerCpu = ConstU128<10>;
+	type BondPerMemory = ConstU128<1>;
+	type BondPerStorage = ConstU128<2>;
+	type BondPerBandwidth = ConstU128<3>;
+	type BondPerAccelerator = ConstU128<100>;
+	type OfflineSlashRatio = OfflineSlashRatio;
+	type ReservationDeposit = ReservationDeposit;
+	type UnbondingPeriod = ConstU64<10>;
+	type EraDuration = ConstU64<20>;
+	type EraRewardPot = ConstU128<1_000>;
+	type RewardPotAccount = RewardPotAccount;
+	type RewardHistoryDepth = ConstU32<2>;
+	type MaxMatchCandidates = ConstU32<8>;
+	type StringLimit = ConstU32<32>;
+	type MaxResourcesPerProvider = ConstU32<4>;
+	type MaxOrdersPerRenter = ConstU32<4>;
+	type MaxLeasesPerResource = ConstU32<3>;
+	type MaxReservationsPerResource = ConstU32<2>;
+	type MaxBidsPerAuction = ConstU32<2>;
+	type MaxAcceptedAssets = ConstU32<2>;
+	type MaxUnbondingChunks = ConstU32<2>;
+	type ThresholdOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetHelper;
+}
+
+/// creates the assets of the benchmarks, sufficient so that their holders need no balance
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<u32, AccountId> for AssetHelper {
+	fn asset(i: u32) -> u32 {
+		i
+	}
+
+	fn fund(asset: u32, who: &AccountId) {
+		use frame_support::traits::tokens::fungibles::{Create, Mutate};
+		// the asset already exists if it has been funded before
+		let _ = <Assets as Create<AccountId>>::create(asset, who.clone(), true, 1);
+		<Assets as Mutate<AccountId>>::mint_into(asset, who, AssetBalance::MAX / 2).unwrap();
+	}
+}
+
+parameter_types! {
+	pub const ProviderPalletId: PalletId = PalletId(*b"py/prvdr");
+	pub const OfflineSlashRatio: Perbill = Perbill::from_percent(50);
+	pub const ReservationDeposit: Perbill = Perbill::from_percent(20);
+	pub const ReputationDecay: Perbill = Perbill::from_percent(50);
+	pub const TargetUtilization: Perbill = Perbill::from_percent(25);
+	pub PriceAdjustmentVariability: FixedU128 = FixedU128::saturating_from_rational(1, 2);
+	pub MinimumPriceMultiplier: FixedU128 = FixedU128::saturating_from_rational(1, 2);
+	pub MaximumPriceMultiplier: FixedU128 = FixedU128::saturating_from_integer(2);
+}
+
+pub const ALICE: AccountId = AccountId32::new([1; 32]);
+pub const BOB: AccountId = AccountId32::new([2; 32]);
+pub const CHARLIE: AccountId = AccountId32::new([3; 32]);
+/// account without any balance
+pub const DAVE: AccountId = AccountId32::new([4; 32]);
+
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
+/// asset held by ALICE, BOB and CHARLIE, it is not sufficient
+pub const ASSET: u32 = 7;
+/// asset that does not exist
+pub const UNKNOWN_ASSET: u32 = 8;
+
+/// public key whose account is `who`, used to sign heartbeats
+pub fn public(who: &AccountId) -> MultiSigner {
+	let raw: [u8; 32] = who.clone().into();
+	sr25519::Public::from_raw(raw).into()
+}
+
+pub fn bounded(bytes: &[u8]) -> BoundedStringOf<Test> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+/// configuration with `cpu` cpus and `memory` memory
+pub fn config(cpu: u64, memory: u64) -> ResourceConfigOf<Test> {
+	ResourceConfig::new(cpu, memory, bounded(b"linux"), bounded(b"Intel Xeon"), Default::default())
+}
+
+pub fn resource(index: u64, who: AccountId, status: ResourceStatus) -> ResourceOf<Test> {
+	let mut peer_id = b"peer".to_vec();
+	peer_id.extend(index.to_string().as_bytes());
+	ComputingResource::new(
+		index,
+		who,
+		bounded(&peer_id),
+		config(2, 4),
+		ResourceRentalStatistics::new(0, 0, 0, 0),
+		ResourceRentalInfo::new(10, 100, 100),
+		status,
+		bounded(b"127.0.0.1"),
+		Specification::General,
+		0,
+	)
+}
+
+#[derive(Default)]
+pub struct ExtBuilder {
+	resources: Vec<(u64, ResourceOf<Test>)>,
+	resource_index: u64,
+	specification_thresholds: Vec<(Specification, SpecificationThreshold)>,
+}
+
+impl ExtBuilder {
+	/// Add resources to the genesis config, the next resource index is set after them.
+	pub fn resources(mut self, resources: Vec<ResourceOf<Test>>) -> Self {
+		self.resource_index = resources.iter().map(|r| r.index + 1).max().unwrap_or_default();
+		self.resources = resources.into_iter().map(|r| (r.index, r)).collect();
+		self
+	}
+
+	pub fn resource_index(mut self, resource_index: u64) -> Self {
+		self.resource_index = resource_index;
+		self
+	}
+
+	pub fn specification_thresholds(
+		mut self,
+		thresholds: Vec<(Specification, SpecificationThreshold)>,
+	) -> Self {
+		self.specification_thresholds = thresholds;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		// the genesis of the whole runtime also puts the storage versions of its pallets
+		let storage = GenesisConfig {
+			system: Default::default(),
+			balances: BalancesConfig {
+				balances: vec![
+					(ALICE, INITIAL_BALANCE),
+					(BOB, INITIAL_BALANCE),
+					(CHARLIE, INITIAL_BALANCE),
+				],
+			},
+			assets: AssetsConfig {
+				assets: vec![(ASSET, ALICE, false, 1)],
+				metadata: vec![],
+				accounts: vec![
+					(ASSET, ALICE, INITIAL_BALANCE as AssetBalance),
+					(ASSET, BOB, INITIAL_BALANCE as AssetBalance),
+					(ASSET, CHARLIE, INITIAL_BALANCE as AssetBalance),
+				],
+			},
+			provider: ProviderConfig {
+				resource: self.resources,
+				resource_index: self.resource_index,
+				specification_thresholds: self.specification_thresholds,
+			},
+		}
+		.build_storage()
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::from(storage);
+		ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+		// events are not deposited in the genesis block
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	pub fn build_and_execute(self, test: impl FnOnce()) {
+		self.build().execute_with(test)
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ExtBuilder::default().build()
+}
+
+/// run the hooks of every block until `n`
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Scheduler::on_initialize(System::block_number());
+		Provider::on_initialize(System::block_number());
+	}
+}
+
+pub fn provider_events() -> Vec<pallet_provider::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Provider(e) = r.event { Some(e) } else { None })
+		.collect()
+}
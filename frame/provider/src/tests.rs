@@ -0,0 +1,3782 @@
+//! Tests for pallet-provider.
+
+use super::*;
+use crate::mock::{Event, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade},
+	StorageHasher,
+};
+use sp_core::{sr25519, H256};
+use sp_hamster::p_provider::ResourceDimension;
+use sp_runtime::{traits::BadOrigin, MultiSignature, MultiSigner, TokenError};
+
+fn register_with(
+	who: AccountId,
+	peer_id: &[u8],
+	config: ResourceConfigOf<Test>,
+	price: Balance,
+) -> u64 {
+	let index = Provider::resource_index();
+	assert_ok!(Provider::register_resource(
+		Origin::signed(who),
+		bounded(peer_id),
+		bounded(b"127.0.0.1"),
+		config,
+		HardwareScores::default(),
+		price,
+		100,
+	));
+	index
+}
+
+/// register a resource with 2 cpus and 4 memory at a unit price of 10 for 100 blocks
+fn register(who: AccountId, peer_id: &[u8]) -> u64 {
+	register_with(who, peer_id, mock::config(2, 4), 10)
+}
+
+/// scores of `score` MB/s in every benchmark
+fn scores(score: u64) -> HardwareScores {
+	HardwareScores {
+		cpu_hashrate_score: score,
+		memory_memcpy_score: score,
+		disk_sequential_write_score: Some(score),
+		disk_random_write_score: Some(score),
+	}
+}
+
+fn threshold(cpu: u64, memory: u64, score: u64) -> SpecificationThreshold {
+	SpecificationThreshold {
+		cpu,
+		memory,
+		cpu_hashrate_score: score,
+		memory_memcpy_score: score,
+		disk_sequential_write_score: score,
+		disk_random_write_score: score,
+	}
+}
+
+/// thresholds of `General`, `Enhanced` and `HighRanking` at 10, 50 and 100 MB/s
+fn thresholds() -> Vec<(Specification, SpecificationThreshold)> {
+	vec![
+		(Specification::General, threshold(1, 1, 10)),
+		(Specification::Enhanced, threshold(2, 4, 50)),
+		(Specification::HighRanking, threshold(4, 8, 100)),
+	]
+}
+
+fn rent(who: AccountId, index: u64, duration: BlockNumber) -> u64 {
+	let order_index = Provider::order_index();
+	assert_ok!(Provider::rent_resource(Origin::signed(who), index, duration));
+	order_index
+}
+
+fn attest(
+	who: &AccountId,
+	index: u64,
+	block_number: BlockNumber,
+	scores: HardwareScores,
+) -> DispatchResult {
+	Provider::attest_hardware(
+		Origin::none(),
+		AttestationPayload { block_number, resource_index: index, scores, public: public(who) },
+		MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+	)
+}
+
+fn answer_from(who: &AccountId, index: u64, peer_id: &[u8], nonce: H256) -> DispatchResult {
+	Provider::answer_challenge(
+		Origin::none(),
+		ChallengeResponsePayload {
+			resource_index: index,
+			nonce,
+			peer_id: peer_id.to_vec(),
+			public: public(who),
+		},
+		MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+	)
+}
+
+/// answer a challenge from the node of the resource
+fn answer(who: &AccountId, index: u64, nonce: H256) -> DispatchResult {
+	let peer_id = Provider::resource(index).map(|r| r.peer_id.into_inner()).unwrap_or_default();
+	answer_from(who, index, &peer_id, nonce)
+}
+
+fn heartbeat(who: &AccountId, index: u64, block_number: BlockNumber) -> DispatchResult {
+	Provider::heartbeat(
+		Origin::none(),
+		HeartbeatPayload { block_number, resource_index: index, public: public(who) },
+		MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+	)
+}
+
+/// fill the queues of `Queue` for every block probed when queueing at `from`
+fn fill_queues<Queue, Bound>(from: BlockNumber)
+where
+	Queue: frame_support::StorageMap<BlockNumber, BoundedVec<u64, Bound>>,
+	Bound: Get<u32>,
+{
+	let full: BoundedVec<u64, Bound> = vec![u64::MAX; Bound::get() as usize].try_into().unwrap();
+	for block in from..from + MAX_QUEUE_PROBES as BlockNumber {
+		Queue::insert(block, full.clone());
+	}
+}
+
+/// resources whose heartbeat deadline passes at `block`
+fn heartbeat_deadlines(block: BlockNumber) -> Vec<u64> {
+	let mut indexes: Vec<u64> = HeartbeatDeadlines::<Test>::iter_key_prefix(block).collect();
+	indexes.sort();
+	indexes
+}
+
+/// end the current era at its last block, without processing the other hooks before it nor
+/// checking the heartbeat deadlines passed in between
+fn end_era() {
+	let now = (Provider::current_era() as BlockNumber + 1) * 20;
+	System::set_block_number(now);
+	HeartbeatCursor::<Test>::put(now);
+	Provider::on_initialize(now);
+}
+
+#[test]
+fn genesis_config_builds_resources() {
+	ExtBuilder::default()
+		.resources(vec![
+			resource(0, ALICE, ResourceStatus::Unused),
+			resource(1, BOB, ResourceStatus::Offline),
+		])
+		.build_and_execute(|| {
+			assert_eq!(Provider::resource_index(), 2);
+			assert_eq!(Provider::resource(0), Some(resource(0, ALICE, ResourceStatus::Unused)));
+			assert_eq!(Provider::resource(1), Some(resource(1, BOB, ResourceStatus::Offline)));
+			assert_eq!(Provider::peer_id_resource(bounded(b"peer0")), Some(0));
+			assert_eq!(Provider::peer_id_resource(bounded(b"peer1")), Some(1));
+			assert_eq!(Provider::provider(ALICE).unwrap().into_inner(), vec![0]);
+			assert_eq!(Provider::provider(BOB).unwrap().into_inner(), vec![1]);
+
+			// both resources are expected to send heartbeats from the genesis block
+			assert_eq!(Provider::resource_heartbeat(0), Some(0));
+			assert_eq!(Provider::resource_heartbeat(1), Some(0));
+			assert_eq!(heartbeat_deadlines(10), vec![0, 1]);
+
+			// offline resources do not earn points
+			assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(6, 6, 0)));
+			assert_eq!(Provider::provider_points(BOB), None);
+			assert_eq!(Provider::total_resource_points(), 6);
+
+			assert_eq!(register(CHARLIE, b"peer2"), 2);
+		});
+}
+
+#[test]
+fn default_genesis_config_is_empty() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Provider::resource_index(), 0);
+		assert_eq!(Resources::<Test>::iter().count(), 0);
+		assert_eq!(Provider::total_resource_points(), 0);
+		assert_eq!(Provider::current_era(), 0);
+	});
+}
+
+#[test]
+fn provider_points_arithmetic() {
+	let mut points = ProviderPoints::new(0, 0, 0);
+	points.add_points(6, 4);
+	assert_eq!(points, ProviderPoints::new(10, 6, 4));
+	points.sub_points(2, 1);
+	assert_eq!(points, ProviderPoints::new(7, 4, 3));
+
+	// points never underflow nor overflow
+	points.sub_points(10, 10);
+	assert_eq!(points, ProviderPoints::new(0, 0, 0));
+	let mut points = ProviderPoints::new(u128::MAX, u64::MAX, u64::MAX);
+	points.add_points(1, 1);
+	assert_eq!(points, ProviderPoints::new(u128::MAX, u64::MAX, u64::MAX));
+}
+
+#[test]
+fn register_resource_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.account_id, ALICE);
+		assert_eq!(resource.status, ResourceStatus::Unused);
+		assert_eq!(resource.rental_info, ResourceRentalInfo::new(10, 100, 101));
+		assert_eq!(resource.registered_at, 1);
+		assert_eq!(Provider::resource_index(), 1);
+		assert_eq!(Provider::peer_id_resource(bounded(b"peer0")), Some(index));
+		assert_eq!(Provider::provider(ALICE).unwrap().into_inner(), vec![index]);
+		assert_eq!(Provider::resource_heartbeat(index), Some(1));
+		assert_eq!(heartbeat_deadlines(11), vec![index]);
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(6, 6, 0)));
+		assert_eq!(Provider::total_resource_points(), 6);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRegistered {
+			who: ALICE,
+			index,
+			peer_id: bounded(b"peer0"),
+		}));
+	});
+}
+
+#[test]
+fn register_resource_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Provider::register_resource(
+				Origin::none(),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				HardwareScores::default(),
+				10,
+				100,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				HardwareScores::default(),
+				10,
+				0,
+			),
+			Error::<Test>::InvalidDuration
+		);
+
+		register(ALICE, b"peer0");
+		assert_noop!(
+			Provider::register_resource(
+				Origin::signed(BOB),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				HardwareScores::default(),
+				10,
+				100,
+			),
+			Error::<Test>::PeerIdAlreadyRegistered
+		);
+
+		register(ALICE, b"peer1");
+		register(ALICE, b"peer2");
+		register(ALICE, b"peer3");
+		assert_noop!(
+			Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer4"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				HardwareScores::default(),
+				10,
+				100,
+			),
+			Error::<Test>::TooManyResources
+		);
+	});
+}
+
+#[test]
+fn register_resource_fails_on_index_overflow() {
+	ExtBuilder::default().resource_index(u64::MAX).build_and_execute(|| {
+		assert_noop!(
+			Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				HardwareScores::default(),
+				10,
+				100,
+			),
+			Error::<Test>::IndexOverflow
+		);
+	});
+}
+
+#[test]
+fn heartbeat_deadlines_past_the_limit_are_checked_in_following_blocks() {
+	new_test_ext().execute_with(|| {
+		for (i, who) in [ALICE, ALICE, ALICE, BOB, BOB].into_iter().enumerate() {
+			register(who, format!("peer{}", i).as_bytes());
+		}
+		assert_eq!(heartbeat_deadlines(11), vec![0, 1, 2, 3, 4]);
+
+		run_to_block(11);
+		let offline = || {
+			Resources::<Test>::iter_values()
+				.filter(|r| r.status == ResourceStatus::Offline)
+				.count()
+		};
+		assert_eq!(offline(), 4);
+		assert_eq!(Provider::heartbeat_cursor(), 11);
+
+		run_to_block(12);
+		assert_eq!(offline(), 5);
+		assert!(heartbeat_deadlines(11).is_empty());
+		assert_eq!(Provider::heartbeat_cursor(), 13);
+	});
+}
+
+#[test]
+fn update_resource_works() {
+	ExtBuilder::default()
+		.specification_thresholds(thresholds())
+		.build_and_execute(|| {
+			let index = Provider::resource_index();
+			assert_ok!(Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(2, 4),
+				scores(10),
+				10,
+				100,
+			));
+
+			assert_ok!(Provider::update_resource(
+				Origin::signed(ALICE),
+				index,
+				Some(bounded(b"10.0.0.1")),
+				Some(mock::config(4, 8)),
+				Some(scores(100)),
+				Some(20),
+			));
+
+			let resource = Provider::resource(index).unwrap();
+			assert_eq!(resource.public_ip, bounded(b"10.0.0.1"));
+			assert_eq!(resource.config, mock::config(4, 8));
+			assert_eq!(resource.specification, Specification::HighRanking);
+			assert_eq!(resource.rental_info.rent_unit_price, 20);
+			assert_eq!(Provider::resource_scores(index), Some(scores(100)));
+			// (4 + 8) * 4
+			assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(48, 48, 0)));
+			assert_eq!(Provider::total_resource_points(), 48);
+			System::assert_last_event(Event::Provider(crate::Event::ResourceUpdated {
+				who: ALICE,
+				index,
+			}));
+		});
+}
+
+#[test]
+fn update_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::update_resource(Origin::signed(ALICE), 1, None, None, None, Some(20)),
+			Error::<Test>::ResourceNotFound
+		);
+		assert_noop!(
+			Provider::update_resource(Origin::signed(BOB), index, None, None, None, Some(20)),
+			Error::<Test>::NotOwner
+		);
+
+		// the hardware of a rented resource can not change, its price can
+		rent(BOB, index, 10);
+		assert_noop!(
+			Provider::update_resource(
+				Origin::signed(ALICE),
+				index,
+				None,
+				Some(mock::config(4, 8)),
+				None,
+				None
+			),
+			Error::<Test>::ResourceInUse
+		);
+		assert_noop!(
+			Provider::update_resource(
+				Origin::signed(ALICE),
+				index,
+				None,
+				None,
+				Some(scores(100)),
+				None
+			),
+			Error::<Test>::ResourceInUse
+		);
+		assert_ok!(Provider::update_resource(
+			Origin::signed(ALICE),
+			index,
+			None,
+			None,
+			None,
+			Some(20)
+		));
+	});
+}
+
+#[test]
+fn specification_is_derived_from_scores() {
+	ExtBuilder::default()
+		.specification_thresholds(thresholds())
+		.build_and_execute(|| {
+			let register_scored = |peer_id: &[u8], config, score| {
+				Provider::register_resource(
+					Origin::signed(ALICE),
+					bounded(peer_id),
+					bounded(b"127.0.0.1"),
+					config,
+					scores(score),
+					10,
+					100,
+				)
+				.map(|_| Provider::resource(Provider::resource_index() - 1).unwrap().specification)
+			};
+
+			assert_eq!(
+				register_scored(b"peer0", mock::config(2, 4), 10),
+				Ok(Specification::General)
+			);
+			assert_eq!(
+				register_scored(b"peer1", mock::config(2, 4), 100),
+				Ok(Specification::Enhanced)
+			);
+			assert_eq!(
+				register_scored(b"peer2", mock::config(4, 8), 100),
+				Ok(Specification::HighRanking)
+			);
+		});
+}
+
+#[test]
+fn hardware_below_general_is_flagged() {
+	ExtBuilder::default()
+		.specification_thresholds(thresholds())
+		.build_and_execute(|| {
+			// unmeasured disks do not reach any threshold
+			assert_ok!(Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(4, 8),
+				HardwareScores { disk_random_write_score: None, ..scores(100) },
+				10,
+				100,
+			));
+			assert_eq!(Provider::resource(0).unwrap().specification, Specification::General);
+			assert_eq!(Provider::flagged_since(0), Some(1));
+			System::assert_has_event(Event::Provider(crate::Event::ResourceFlagged { index: 0 }));
+			assert_noop!(
+				Provider::rent_resource(Origin::signed(BOB), 0, 10),
+				Error::<Test>::ResourceFlagged
+			);
+
+			assert_ok!(Provider::update_resource(
+				Origin::signed(ALICE),
+				0,
+				None,
+				None,
+				Some(scores(100)),
+				None
+			));
+			assert_eq!(Provider::resource(0).unwrap().specification, Specification::HighRanking);
+			assert_eq!(Provider::flagged_since(0), None);
+			System::assert_has_event(Event::Provider(crate::Event::ResourceUnflagged { index: 0 }));
+
+			assert_ok!(Provider::update_resource(
+				Origin::signed(ALICE),
+				0,
+				None,
+				None,
+				Some(scores(5)),
+				None
+			));
+			assert_eq!(Provider::resource(0).unwrap().specification, Specification::General);
+			assert_eq!(Provider::flagged_since(0), Some(1));
+
+			// a resource left behind by a raised threshold is flagged the same way
+			assert_ok!(Provider::update_resource(
+				Origin::signed(ALICE),
+				0,
+				None,
+				None,
+				Some(scores(10)),
+				None
+			));
+			assert_eq!(Provider::flagged_since(0), None);
+			System::set_block_number(5);
+			assert_ok!(Provider::set_specification_threshold(
+				Origin::root(),
+				Specification::General,
+				Some(threshold(1, 1, 20)),
+			));
+			assert_ok!(Provider::refresh_specification(Origin::signed(BOB), 0));
+			assert_eq!(Provider::resource(0).unwrap().specification, Specification::General);
+			assert_eq!(Provider::flagged_since(0), Some(5));
+		});
+}
+
+#[test]
+fn set_specification_threshold_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Provider::set_specification_threshold(
+				Origin::signed(ALICE),
+				Specification::Enhanced,
+				Some(threshold(2, 4, 50)),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(Provider::set_specification_threshold(
+			Origin::root(),
+			Specification::Enhanced,
+			Some(threshold(2, 4, 50)),
+		));
+		assert_eq!(
+			Provider::specification_threshold(Specification::Enhanced),
+			Some(threshold(2, 4, 50))
+		);
+		System::assert_last_event(Event::Provider(crate::Event::SpecificationThresholdSet {
+			specification: Specification::Enhanced,
+			threshold: Some(threshold(2, 4, 50)),
+		}));
+
+		assert_ok!(Provider::set_specification_threshold(
+			Origin::root(),
+			Specification::Enhanced,
+			None,
+		));
+		assert_eq!(Provider::specification_threshold(Specification::Enhanced), None);
+	});
+}
+
+#[test]
+fn refresh_specification_works() {
+	new_test_ext().execute_with(|| {
+		let index = Provider::resource_index();
+		assert_ok!(Provider::register_resource(
+			Origin::signed(ALICE),
+			bounded(b"peer0"),
+			bounded(b"127.0.0.1"),
+			mock::config(2, 4),
+			scores(50),
+			10,
+			100,
+		));
+		// without thresholds every resource is `General`
+		assert_eq!(Provider::resource(index).unwrap().specification, Specification::General);
+		assert_eq!(Provider::total_resource_points(), 6);
+
+		assert_noop!(
+			Provider::refresh_specification(Origin::signed(BOB), 1),
+			Error::<Test>::ResourceNotFound
+		);
+
+		assert_ok!(Provider::set_specification_threshold(
+			Origin::root(),
+			Specification::Enhanced,
+			Some(threshold(2, 4, 50)),
+		));
+		assert_ok!(Provider::refresh_specification(Origin::signed(BOB), index));
+		assert_eq!(Provider::resource(index).unwrap().specification, Specification::Enhanced);
+		// (2 + 4) * 2
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(12, 12, 0)));
+		assert_eq!(Provider::total_resource_points(), 12);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceSpecificationChanged {
+			index,
+			specification: Specification::Enhanced,
+		}));
+
+		// a rented resource keeps its specification until the lease ends
+		assert_ok!(Provider::set_specification_threshold(
+			Origin::root(),
+			Specification::Enhanced,
+			Some(threshold(2, 4, 60)),
+		));
+		rent(BOB, index, 10);
+		assert_noop!(
+			Provider::refresh_specification(Origin::signed(BOB), index),
+			Error::<Test>::ResourceInUse
+		);
+	});
+}
+
+#[test]
+fn add_resource_duration_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_ok!(Provider::add_resource_duration(Origin::signed(ALICE), index, 50));
+		assert_eq!(
+			Provider::resource(index).unwrap().rental_info,
+			ResourceRentalInfo::new(10, 150, 151)
+		);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceDurationAdded {
+			who: ALICE,
+			index,
+			duration: 50,
+		}));
+
+		assert_noop!(
+			Provider::add_resource_duration(Origin::signed(ALICE), index, 0),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			Provider::add_resource_duration(Origin::signed(BOB), index, 50),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Provider::add_resource_duration(Origin::signed(ALICE), 1, 50),
+			Error::<Test>::ResourceNotFound
+		);
+	});
+}
+
+#[test]
+fn remove_resource_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		register(BOB, b"peer1");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+
+		assert_ok!(Provider::remove_resource(Origin::signed(ALICE), index));
+
+		assert_eq!(Provider::resource(index), None);
+		assert_eq!(Provider::peer_id_resource(bounded(b"peer0")), None);
+		assert_eq!(Provider::provider(ALICE), None);
+		assert_eq!(Provider::resource_heartbeat(index), None);
+		assert_eq!(Provider::provider_points(ALICE), None);
+		assert_eq!(Provider::total_resource_points(), 6);
+		// the bond is unbonded rather than released
+		assert_eq!(Provider::resource_bond(index), None);
+		assert_eq!(Provider::unbonding(ALICE).unwrap().into_inner(), vec![(11, 24)]);
+		assert_eq!(Balances::reserved_balance(ALICE), 24);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRemoved {
+			who: ALICE,
+			index,
+		}));
+
+		// the removed deadline is ignored
+		run_to_block(11);
+		assert_eq!(Provider::resource(index), None);
+	});
+}
+
+#[test]
+fn remove_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(ALICE), 1),
+			Error::<Test>::ResourceNotFound
+		);
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(BOB), index),
+			Error::<Test>::NotOwner
+		);
+
+		rent(BOB, index, 10);
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(ALICE), index),
+			Error::<Test>::ResourceInUse
+		);
+
+		Resources::<Test>::mutate(index, |r| {
+			r.as_mut().unwrap().update_status(ResourceStatus::Locked)
+		});
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(ALICE), index),
+			Error::<Test>::ResourceInUse
+		);
+	});
+}
+
+#[test]
+fn bond_resource_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		// (10 * 2 + 4) * 1
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		assert_eq!(Provider::resource_bond(index), Some(24));
+		assert_eq!(Balances::reserved_balance(ALICE), 24);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceBonded {
+			index,
+			amount: 24,
+		}));
+		assert_noop!(
+			Provider::bond_resource(Origin::signed(ALICE), index),
+			Error::<Test>::AlreadyBonded
+		);
+
+		// a bigger resource tops its bond up, only the difference is reserved
+		assert_ok!(Provider::update_resource(
+			Origin::signed(ALICE),
+			index,
+			None,
+			Some(mock::config(4, 8)),
+			None,
+			None,
+		));
+		assert_eq!(Provider::resource_bond(index), Some(48));
+		assert_eq!(Balances::reserved_balance(ALICE), 48);
+		System::assert_has_event(Event::Provider(crate::Event::ResourceBonded {
+			index,
+			amount: 48,
+		}));
+		assert_noop!(
+			Provider::bond_resource(Origin::signed(ALICE), index),
+			Error::<Test>::AlreadyBonded
+		);
+
+		// a smaller resource keeps its bond
+		assert_ok!(Provider::update_resource(
+			Origin::signed(ALICE),
+			index,
+			None,
+			Some(mock::config(2, 4)),
+			None,
+			None,
+		));
+		assert_eq!(Provider::resource_bond(index), Some(48));
+	});
+}
+
+#[test]
+fn update_resource_fails_without_bond_top_up() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		Balances::make_free_balance_be(&ALICE, 10);
+
+		assert_noop!(
+			Provider::update_resource(
+				Origin::signed(ALICE),
+				index,
+				None,
+				Some(mock::config(4, 8)),
+				None,
+				None,
+			),
+			Error::<Test>::InsufficientBond
+		);
+
+		// an unbonded resource does not need any bond to grow
+		let index = register(DAVE, b"peer1");
+		assert_ok!(Provider::update_resource(
+			Origin::signed(DAVE),
+			index,
+			None,
+			Some(mock::config(4, 8)),
+			None,
+			None,
+		));
+		assert_eq!(Provider::resource_bond(index), None);
+	});
+}
+
+#[test]
+fn bond_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(DAVE, b"peer0");
+
+		assert_noop!(
+			Provider::bond_resource(Origin::signed(ALICE), index),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Provider::bond_resource(Origin::signed(DAVE), index),
+			Error::<Test>::InsufficientBond
+		);
+		assert_noop!(
+			Provider::bond_resource(Origin::signed(DAVE), 1),
+			Error::<Test>::ResourceNotFound
+		);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+
+		assert_ok!(Provider::unbond_resource(Origin::signed(ALICE), index));
+		assert_eq!(Provider::resource_bond(index), None);
+		assert_eq!(Provider::unbonding(ALICE).unwrap().into_inner(), vec![(11, 24)]);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceUnbonded {
+			index,
+			amount: 24,
+			unlock_at: 11,
+		}));
+		assert_noop!(
+			Provider::unbond_resource(Origin::signed(ALICE), index),
+			Error::<Test>::NotBonded
+		);
+
+		// the collateral stays reserved until the end of the unbonding period
+		run_to_block(10);
+		assert_noop!(
+			Provider::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Test>::NoUnbondedFunds
+		);
+		run_to_block(11);
+		assert_ok!(Provider::withdraw_unbonded(Origin::signed(ALICE)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Provider::unbonding(ALICE), None);
+		System::assert_last_event(Event::Provider(crate::Event::Withdrawn {
+			who: ALICE,
+			amount: 24,
+		}));
+		assert_noop!(
+			Provider::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Test>::NoUnbondedFunds
+		);
+	});
+}
+
+#[test]
+fn unbond_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let indexes: Vec<u64> =
+			(0..3).map(|i| register(ALICE, format!("peer{}", i).as_bytes())).collect();
+		for index in &indexes {
+			assert_ok!(Provider::bond_resource(Origin::signed(ALICE), *index));
+		}
+
+		assert_noop!(
+			Provider::unbond_resource(Origin::signed(BOB), indexes[0]),
+			Error::<Test>::NotOwner
+		);
+
+		rent(BOB, indexes[0], 10);
+		assert_noop!(
+			Provider::unbond_resource(Origin::signed(ALICE), indexes[0]),
+			Error::<Test>::ResourceInUse
+		);
+
+		assert_ok!(Provider::unbond_resource(Origin::signed(ALICE), indexes[1]));
+		assert_ok!(Provider::unbond_resource(Origin::signed(ALICE), indexes[2]));
+		let index = register(ALICE, b"peer3");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		assert_noop!(
+			Provider::unbond_resource(Origin::signed(ALICE), index),
+			Error::<Test>::TooManyUnbondingChunks
+		);
+	});
+}
+
+#[test]
+fn rent_resource_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		let order_index = rent(BOB, index, 10);
+
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		let order = Provider::rental_order(order_index).unwrap();
+		assert_eq!(order.renter, BOB);
+		assert_eq!(order.slice, ResourceSlice::new(2, 4));
+		assert_eq!(order.provider, ALICE);
+		assert_eq!(order.resource_index, index);
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(10, 10, 11));
+		assert_eq!(order.deposit, 100);
+		assert_eq!(Provider::order_index(), 1);
+		assert_eq!(Provider::renter_orders(BOB).unwrap().into_inner(), vec![order_index]);
+		assert_eq!(Provider::resource_leases(index).unwrap().into_inner(), vec![order_index]);
+		assert_eq!(Provider::order_expiries(11).into_inner(), vec![order_index]);
+
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.status, ResourceStatus::Inuse);
+		assert_eq!(resource.allocated, ResourceSlice::new(2, 4));
+		assert_eq!(resource.rental_statistics.rental_count, 1);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRented {
+			renter: BOB,
+			resource_index: index,
+			order_index,
+			slice: ResourceSlice::new(2, 4),
+			end_of_rent: 11,
+			deposit: 100,
+			asset_id: None,
+		}));
+	});
+}
+
+#[test]
+fn rent_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 0),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), 1, 10),
+			Error::<Test>::ResourceNotFound
+		);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(ALICE), index, 10),
+			Error::<Test>::CannotRentOwnResource
+		);
+		// the resource can be rented until block 101
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 101),
+			Error::<Test>::ExceedsRentableDuration
+		);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(DAVE), index, 10),
+			Error::<Test>::InsufficientBalance
+		);
+
+		fill_queues::<OrderExpiries<Test>, _>(11);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 10),
+			Error::<Test>::QueueFull
+		);
+
+		rent(BOB, index, 100);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(CHARLIE), index, 10),
+			Error::<Test>::ResourceNotAvailable
+		);
+	});
+}
+
+#[test]
+fn rent_resource_fails_on_price_overflow() {
+	new_test_ext().execute_with(|| {
+		let index = register_with(ALICE, b"peer0", mock::config(2, 4), u128::MAX);
+
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 2),
+			Error::<Test>::PriceOverflow
+		);
+	});
+}
+
+#[test]
+fn rent_resource_fails_with_too_many_orders() {
+	new_test_ext().execute_with(|| {
+		for i in 0..4 {
+			let index = register(ALICE, format!("peer{}", i).as_bytes());
+			rent(BOB, index, 10);
+		}
+		let index = register(CHARLIE, b"peer4");
+
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 10),
+			Error::<Test>::TooManyOrders
+		);
+	});
+}
+
+fn rent_slice(who: AccountId, index: u64, cpu: u64, memory: u64, duration: BlockNumber) -> u64 {
+	let order_index = Provider::order_index();
+	assert_ok!(Provider::rent_slice(
+		Origin::signed(who),
+		index,
+		ResourceSlice::new(cpu, memory),
+		duration
+	));
+	order_index
+}
+
+#[test]
+fn set_resource_divisible_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert!(!Provider::resource(index).unwrap().divisible);
+
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+
+		assert!(Provider::resource(index).unwrap().divisible);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceDivisibilitySet {
+			index,
+			divisible: true,
+		}));
+	});
+}
+
+#[test]
+fn set_resource_divisible_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::set_resource_divisible(Origin::signed(ALICE), 1, true),
+			Error::<Test>::ResourceNotFound
+		);
+		assert_noop!(
+			Provider::set_resource_divisible(Origin::signed(BOB), index, true),
+			Error::<Test>::NotOwner
+		);
+
+		// a resource that is leased, even partially, keeps its divisibility
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		rent_slice(BOB, index, 1, 1, 10);
+		assert_noop!(
+			Provider::set_resource_divisible(Origin::signed(ALICE), index, false),
+			Error::<Test>::ResourceInUse
+		);
+	});
+}
+
+#[test]
+fn rent_slice_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+
+		// half of the resource is leased at half of its unit price
+		let first = rent_slice(BOB, index, 1, 2, 10);
+		let order = Provider::rental_order(first).unwrap();
+		assert_eq!(order.slice, ResourceSlice::new(1, 2));
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(5, 10, 11));
+		assert_eq!(order.deposit, 50);
+		assert_eq!(Balances::reserved_balance(BOB), 50);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRented {
+			renter: BOB,
+			resource_index: index,
+			order_index: first,
+			slice: ResourceSlice::new(1, 2),
+			end_of_rent: 11,
+			deposit: 50,
+			asset_id: None,
+		}));
+
+		// the resource stays available until all of it is allocated
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.allocated, ResourceSlice::new(1, 2));
+		assert_eq!(resource.available(), ResourceSlice::new(1, 2));
+		assert_eq!(resource.status, ResourceStatus::Unused);
+
+		let second = rent_slice(CHARLIE, index, 1, 2, 10);
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.allocated, ResourceSlice::new(2, 4));
+		assert_eq!(resource.status, ResourceStatus::Inuse);
+		assert_eq!(resource.rental_statistics.rental_count, 2);
+		assert_eq!(Provider::resource_leases(index).unwrap().into_inner(), vec![first, second]);
+
+		// terminating a lease releases its slice, the points are scaled by the slice
+		run_to_block(5);
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), first));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 20);
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(8, 6, 2)));
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.allocated, ResourceSlice::new(1, 2));
+		assert_eq!(resource.status, ResourceStatus::Unused);
+		assert_eq!(Provider::resource_leases(index).unwrap().into_inner(), vec![second]);
+	});
+}
+
+#[test]
+fn rent_slice_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(BOB), index, ResourceSlice::new(1, 1), 10),
+			Error::<Test>::ResourceNotDivisible
+		);
+
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(BOB), index, ResourceSlice::new(0, 0), 10),
+			Error::<Test>::InvalidSlice
+		);
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(BOB), index, ResourceSlice::new(3, 1), 10),
+			Error::<Test>::InsufficientCapacity
+		);
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(ALICE), index, ResourceSlice::new(1, 1), 10),
+			Error::<Test>::CannotRentOwnResource
+		);
+
+		// a partially leased resource can only be leased in slices
+		rent_slice(BOB, index, 1, 3, 10);
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(CHARLIE), index, ResourceSlice::new(1, 2), 10),
+			Error::<Test>::InsufficientCapacity
+		);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(CHARLIE), index, 10),
+			Error::<Test>::ResourceNotAvailable
+		);
+
+		rent_slice(CHARLIE, index, 0, 1, 10);
+		rent_slice(BOB, index, 1, 0, 10);
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(CHARLIE), index, ResourceSlice::new(0, 1), 10),
+			Error::<Test>::ResourceNotAvailable
+		);
+	});
+}
+
+#[test]
+fn rent_slice_fails_with_too_many_leases() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		for _ in 0..3 {
+			rent_slice(BOB, index, 0, 1, 10);
+		}
+
+		assert_noop!(
+			Provider::rent_slice(Origin::signed(CHARLIE), index, ResourceSlice::new(1, 1), 10),
+			Error::<Test>::TooManyLeases
+		);
+	});
+}
+
+#[test]
+fn rent_by_demand_picks_the_best_resource() {
+	new_test_ext().execute_with(|| {
+		let small = register(ALICE, b"peer0");
+		let cheap = register_with(CHARLIE, b"peer1", mock::config(4, 8), 5);
+		register_with(CHARLIE, b"peer2", mock::config(1, 1), 1);
+		let demand =
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default());
+
+		// the cheapest satisfying resource is rented
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			demand.clone(),
+			10,
+			10,
+			MatchPolicy::Cheapest,
+		));
+		assert_eq!(Provider::resource(cheap).unwrap().status, ResourceStatus::Inuse);
+		assert_eq!(Provider::rental_order(0).unwrap().deposit, 50);
+
+		// resources of the renter are never matched
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(ALICE),
+				demand.clone(),
+				10,
+				10,
+				MatchPolicy::Cheapest,
+			),
+			Error::<Test>::NoMatchingResource
+		);
+
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			demand,
+			10,
+			10,
+			MatchPolicy::Cheapest,
+		));
+		assert_eq!(Provider::resource(small).unwrap().status, ResourceStatus::Inuse);
+	});
+}
+
+#[test]
+fn rent_by_demand_looks_past_the_resources_not_matching() {
+	new_test_ext().execute_with(|| {
+		// all the `MaxMatchCandidates` unused resources but one are too expensive, rented
+		// resources are not candidates
+		for id in 10..17 {
+			let who = AccountId::new([id; 32]);
+			register_with(who, format!("peer{}", id).as_bytes(), mock::config(2, 4), 100);
+		}
+		let rented = register(CHARLIE, b"rented");
+		rent(BOB, rented, 10);
+		assert!(!UnusedResources::<Test>::contains_key(Specification::General, rented));
+		let index = register(ALICE, b"peer0");
+		assert!(UnusedResources::<Test>::contains_key(Specification::General, index));
+
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default()),
+			10,
+			10,
+			MatchPolicy::Cheapest,
+		));
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Inuse);
+		assert!(!UnusedResources::<Test>::contains_key(Specification::General, index));
+
+		// resources are matched again once released
+		run_to_block(10);
+		assert_ok!(heartbeat(&ALICE, index, 10));
+		run_to_block(11);
+		assert!(UnusedResources::<Test>::contains_key(Specification::General, index));
+	});
+}
+
+#[test]
+fn rent_by_demand_reads_at_most_max_match_candidates() {
+	// the `MaxMatchCandidates` general resources are too expensive and read before the
+	// enhanced one
+	let mut resources: Vec<_> = (0..8)
+		.map(|index| ComputingResource {
+			rental_info: ResourceRentalInfo::new(100, 100, 100),
+			..resource(index, AccountId::new([10 + index as u8; 32]), ResourceStatus::Unused)
+		})
+		.collect();
+	resources.push(ComputingResource {
+		specification: Specification::Enhanced,
+		..resource(8, ALICE, ResourceStatus::Unused)
+	});
+	ExtBuilder::default().resources(resources).build_and_execute(|| {
+		let demand =
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default());
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				demand.clone(),
+				10,
+				10,
+				MatchPolicy::Cheapest,
+			),
+			Error::<Test>::NoMatchingResource
+		);
+
+		assert_ok!(Provider::remove_resource(Origin::signed(AccountId::new([10; 32])), 0));
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			demand,
+			10,
+			10,
+			MatchPolicy::Cheapest,
+		));
+		assert_eq!(Provider::resource(8).unwrap().status, ResourceStatus::Inuse);
+	});
+}
+
+#[test]
+fn rent_by_demand_leases_slices_of_divisible_resources() {
+	new_test_ext().execute_with(|| {
+		let index = register_with(ALICE, b"peer0", mock::config(4, 8), 10);
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		let demand =
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default());
+
+		// the slice is priced at its share of the resource
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				demand.clone(),
+				4,
+				10,
+				MatchPolicy::Cheapest,
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		for _ in 0..2 {
+			assert_ok!(Provider::rent_by_demand(
+				Origin::signed(BOB),
+				demand.clone(),
+				5,
+				10,
+				MatchPolicy::Cheapest,
+			));
+		}
+
+		let order = Provider::rental_order(0).unwrap();
+		assert_eq!(order.slice, ResourceSlice::new(2, 4));
+		assert_eq!(order.deposit, 50);
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.allocated, ResourceSlice::new(4, 8));
+		assert_eq!(resource.status, ResourceStatus::Inuse);
+
+		// nothing is left for another slice
+		assert_noop!(
+			Provider::rent_by_demand(Origin::signed(BOB), demand, 5, 10, MatchPolicy::Cheapest),
+			Error::<Test>::NoMatchingResource
+		);
+	});
+}
+
+#[test]
+fn rent_by_demand_prefers_fewer_faults() {
+	new_test_ext().execute_with(|| {
+		let reliable = register(ALICE, b"peer0");
+		let faulty = register_with(CHARLIE, b"peer1", mock::config(2, 4), 5);
+		Resources::<Test>::mutate(faulty, |r| {
+			r.as_mut().unwrap().rental_statistics.add_fault_count()
+		});
+
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default()),
+			10,
+			10,
+			MatchPolicy::BestReputation,
+		));
+		assert_eq!(Provider::resource(reliable).unwrap().status, ResourceStatus::Inuse);
+		assert_eq!(Provider::resource(faulty).unwrap().status, ResourceStatus::Unused);
+	});
+}
+
+#[test]
+fn reputation_counts_leases_faults_and_ratings() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		// neutral without any history
+		assert_eq!(Provider::current_reputation(index).unwrap().score, Perbill::from_percent(50));
+		assert_eq!(Provider::current_reputation(1), None);
+
+		let order_index = rent(BOB, index, 5);
+		assert_ok!(Provider::rate_order(Origin::signed(BOB), order_index, 4));
+		assert_eq!(Provider::order_rating(order_index), Some(4));
+		let reputation = Provider::resource_reputation(index).unwrap();
+		assert_eq!((reputation.ratings, reputation.rating_count), (800, 1_000));
+		assert_eq!(reputation.score, Perbill::from_percent(80));
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRated {
+			index,
+			order_index,
+			rating: 4,
+		}));
+
+		// the reputation is stored when the lease ends
+		run_to_block(6);
+		assert_eq!(Provider::order_rating(order_index), None);
+		let reputation = Provider::resource_reputation(index).unwrap();
+		assert_eq!((reputation.leased, reputation.faulty), (5, 0));
+		assert_eq!(reputation.score, Perbill::from_percent(90));
+
+		// faults count their duration and a penalty, stored once refreshed
+		Resources::<Test>::mutate(index, |r| {
+			let statistics = &mut r.as_mut().unwrap().rental_statistics;
+			statistics.add_fault_count();
+			statistics.add_fault_duration(5);
+		});
+		let reliability = Perbill::from_rational(5u64, 15);
+		let score = Perbill::from_parts((reliability.deconstruct() + 800_000_000) / 2);
+		assert_eq!(Provider::current_reputation(index).unwrap().score, score);
+		assert_eq!(Provider::resource_reputation(index).unwrap().score, Perbill::from_percent(90));
+		assert_ok!(Provider::refresh_reputation(Origin::signed(CHARLIE), index));
+		assert_eq!(Provider::resource_reputation(index).unwrap().faulty, 10);
+		System::assert_last_event(Event::Provider(crate::Event::ReputationUpdated {
+			index,
+			score,
+		}));
+		assert_noop!(
+			Provider::refresh_reputation(Origin::signed(CHARLIE), 1),
+			Error::<Test>::ResourceNotFound
+		);
+	});
+}
+
+#[test]
+fn reputation_decays_every_era() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		ResourceReputations::<Test>::insert(
+			index,
+			Reputation { leased: 80, ratings: 400, rating_count: 1_000, ..Reputation::new(0) },
+		);
+
+		end_era();
+		end_era();
+		let reputation = Provider::current_reputation(index).unwrap();
+		assert_eq!(reputation.era, 2);
+		assert_eq!(
+			(reputation.leased, reputation.ratings, reputation.rating_count),
+			(20, 100, 250)
+		);
+
+		// recent faults weigh more than older leases
+		Resources::<Test>::mutate(index, |r| {
+			r.as_mut().unwrap().rental_statistics.add_fault_duration(15)
+		});
+		let reputation = Provider::current_reputation(index).unwrap();
+		assert_eq!(reputation.faulty, 15);
+		assert_eq!(
+			reputation.score,
+			Perbill::from_parts(
+				(Perbill::from_rational(20u64, 35).deconstruct() + 400_000_000) / 2
+			)
+		);
+	});
+}
+
+#[test]
+fn rate_order_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 5);
+
+		assert_noop!(Provider::rate_order(Origin::signed(BOB), 1, 3), Error::<Test>::OrderNotFound);
+		assert_noop!(
+			Provider::rate_order(Origin::signed(ALICE), order_index, 3),
+			Error::<Test>::NotRenter
+		);
+		assert_noop!(
+			Provider::rate_order(Origin::signed(BOB), order_index, 0),
+			Error::<Test>::InvalidRating
+		);
+		assert_noop!(
+			Provider::rate_order(Origin::signed(BOB), order_index, MAX_RATING + 1),
+			Error::<Test>::InvalidRating
+		);
+		assert_ok!(Provider::rate_order(Origin::signed(BOB), order_index, 3));
+		assert_noop!(
+			Provider::rate_order(Origin::signed(BOB), order_index, 5),
+			Error::<Test>::AlreadyRated
+		);
+	});
+}
+
+#[test]
+fn resources_and_providers_are_ranked_by_reputation() {
+	new_test_ext().execute_with(|| {
+		let neutral = register(ALICE, b"peer0");
+		let rated = register(ALICE, b"peer1");
+		let order_index = rent(BOB, rated, 5);
+		assert_ok!(Provider::rate_order(Origin::signed(BOB), order_index, MAX_RATING));
+
+		assert_eq!(Provider::provider_reputation(&ALICE), Some(Perbill::from_percent(75)));
+		assert_eq!(Provider::provider_reputation(&BOB), None);
+
+		let ranked = |min_score| {
+			Provider::resources_by_reputation(min_score)
+				.into_iter()
+				.map(|(resource, reputation)| (resource.index, reputation.score))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			ranked(Perbill::zero()),
+			vec![(rated, Perbill::one()), (neutral, Perbill::from_percent(50))]
+		);
+		assert_eq!(ranked(Perbill::from_percent(60)), vec![(rated, Perbill::one())]);
+	});
+}
+
+#[test]
+fn rent_by_demand_fails() {
+	new_test_ext().execute_with(|| {
+		register(ALICE, b"peer0");
+		let demand =
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default());
+
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				demand.clone(),
+				10,
+				0,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::InvalidDuration
+		);
+		// too expensive
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				demand.clone(),
+				9,
+				10,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		// too long
+		assert_noop!(
+			Provider::rent_by_demand(Origin::signed(BOB), demand, 10, 101, MatchPolicy::Cheapest),
+			Error::<Test>::NoMatchingResource
+		);
+		// too small
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				ResourceDemand::new(4, 4, Specification::General, None, None, Default::default()),
+				10,
+				10,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				ResourceDemand::new(2, 4, Specification::Enhanced, None, None, Default::default()),
+				10,
+				10,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(BOB),
+				ResourceDemand::new(
+					2,
+					4,
+					Specification::General,
+					Some(bounded(b"windows")),
+					None,
+					Default::default()
+				),
+				10,
+				10,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(DAVE),
+				ResourceDemand::new(
+					2,
+					4,
+					Specification::General,
+					None,
+					Some(bounded(b"Intel Xeon")),
+					Default::default()
+				),
+				10,
+				10,
+				MatchPolicy::Cheapest
+			),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+/// configuration with `cpu` cpus, `memory` memory and `dimensions`
+fn config_with(
+	cpu: u64,
+	memory: u64,
+	dimensions: Vec<ResourceDimension<<Test as Config>::StringLimit>>,
+) -> ResourceConfigOf<Test> {
+	ResourceConfig { dimensions: dimensions.try_into().unwrap(), ..mock::config(cpu, memory) }
+}
+
+fn accelerator(
+	model: &[u8],
+	count: u32,
+	memory: u64,
+) -> ResourceDimension<<Test as Config>::StringLimit> {
+	ResourceDimension::Accelerator { model: bounded(model), count, memory }
+}
+
+#[test]
+fn config_dimensions_are_summed() {
+	let config = config_with(
+		2,
+		4,
+		vec![
+			ResourceDimension::Storage { kind: DiskKind::Hdd, capacity: 1000 },
+			ResourceDimension::Storage { kind: DiskKind::Nvme, capacity: 100 },
+			ResourceDimension::Bandwidth(1000),
+			accelerator(b"A100", 2, 80),
+			accelerator(b"T4", 1, 16),
+		],
+	);
+
+	// faster disks count as slower ones
+	assert_eq!(config.storage(DiskKind::Hdd), 1100);
+	assert_eq!(config.storage(DiskKind::Ssd), 100);
+	assert_eq!(config.bandwidth(), 1000);
+	assert_eq!(config.accelerators(None), 3);
+	assert_eq!(config.accelerators(Some((b"A100", 40))), 2);
+	assert_eq!(config.accelerators(Some((b"A100", 96))), 0);
+
+	assert!(config.provides(&ResourceDimension::Storage { kind: DiskKind::Ssd, capacity: 100 }));
+	assert!(!config.provides(&ResourceDimension::Storage { kind: DiskKind::Ssd, capacity: 101 }));
+	assert!(config.provides(&ResourceDimension::Bandwidth(1000)));
+	assert!(!config.provides(&ResourceDimension::Bandwidth(1001)));
+	assert!(config.provides(&accelerator(b"A100", 2, 80)));
+	assert!(!config.provides(&accelerator(b"A100", 3, 80)));
+	assert!(!config.provides(&accelerator(b"H100", 1, 80)));
+}
+
+#[test]
+fn rent_by_demand_matches_dimensions() {
+	new_test_ext().execute_with(|| {
+		register(ALICE, b"peer0");
+		let gpu = register_with(
+			CHARLIE,
+			b"peer1",
+			config_with(
+				2,
+				4,
+				vec![ResourceDimension::Bandwidth(1000), accelerator(b"A100", 2, 80)],
+			),
+			20,
+		);
+		let demand = ResourceDemand::new(
+			2,
+			4,
+			Specification::General,
+			None,
+			None,
+			vec![ResourceDimension::Bandwidth(500), accelerator(b"A100", 1, 80)]
+				.try_into()
+				.unwrap(),
+		);
+
+		assert_eq!(
+			Provider::resources_matching(&demand, None),
+			vec![Provider::resource(gpu).unwrap()]
+		);
+
+		// the cheaper resource without accelerators is not matched
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(BOB),
+			demand.clone(),
+			20,
+			10,
+			MatchPolicy::Cheapest,
+		));
+		assert_eq!(Provider::resource(gpu).unwrap().status, ResourceStatus::Inuse);
+		assert!(Provider::resources_matching(&demand, Some(ResourceStatus::Unused)).is_empty());
+	});
+}
+
+#[test]
+fn invalid_dimensions_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		for dimension in [
+			ResourceDimension::Storage { kind: DiskKind::Ssd, capacity: 0 },
+			ResourceDimension::Bandwidth(0),
+			accelerator(b"A100", 0, 80),
+			accelerator(b"", 1, 80),
+		] {
+			let config = config_with(2, 4, vec![dimension]);
+			assert_noop!(
+				Provider::register_resource(
+					Origin::signed(ALICE),
+					bounded(b"peer1"),
+					bounded(b"127.0.0.1"),
+					config.clone(),
+					HardwareScores::default(),
+					10,
+					100,
+				),
+				Error::<Test>::InvalidDimension
+			);
+			assert_noop!(
+				Provider::update_resource(
+					Origin::signed(ALICE),
+					index,
+					None,
+					Some(config),
+					None,
+					None
+				),
+				Error::<Test>::InvalidDimension
+			);
+		}
+	});
+}
+
+#[test]
+fn bond_covers_every_dimension() {
+	new_test_ext().execute_with(|| {
+		let config = config_with(
+			2,
+			4,
+			vec![
+				ResourceDimension::Storage { kind: DiskKind::Ssd, capacity: 10 },
+				ResourceDimension::Bandwidth(5),
+				accelerator(b"A100", 2, 80),
+			],
+		);
+		let index = register_with(ALICE, b"peer0", config, 10);
+
+		// (10 * 2 + 4 + 2 * 10 + 3 * 5 + 100 * 2) * 1
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		assert_eq!(Provider::resource_bond(index), Some(259));
+	});
+}
+
+#[test]
+fn orders_are_settled_when_they_expire() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 5);
+
+		run_to_block(5);
+		assert!(Provider::rental_order(order_index).is_some());
+
+		run_to_block(6);
+		assert_eq!(Provider::rental_order(order_index), None);
+		assert_eq!(Provider::resource_leases(index), None);
+		assert_eq!(Provider::renter_orders(BOB), None);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 50);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.status, ResourceStatus::Unused);
+		assert_eq!(resource.rental_statistics.rental_duration, 5);
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(11, 6, 5)));
+		assert_eq!(Provider::total_duration_points(), 5);
+		System::assert_last_event(Event::Provider(crate::Event::OrderSettled {
+			order_index,
+			provider: ALICE,
+			amount: 50,
+		}));
+	});
+}
+
+#[test]
+fn orders_are_settled_for_what_is_still_reserved() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 5);
+		// part of the deposit is no longer reserved by the renter
+		<Balances as ReservableCurrency<_>>::unreserve(&BOB, 20);
+
+		run_to_block(6);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 30);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 30);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		System::assert_last_event(Event::Provider(crate::Event::OrderSettled {
+			order_index,
+			provider: ALICE,
+			amount: 30,
+		}));
+	});
+}
+
+#[test]
+fn order_expiries_overflow_into_following_blocks() {
+	new_test_ext().execute_with(|| {
+		for i in 0..4 {
+			let index = register(ALICE, format!("peer{}", i).as_bytes());
+			rent(BOB, index, 5);
+		}
+		let index = register(CHARLIE, b"peer4");
+		rent(ALICE, index, 5);
+
+		assert_eq!(Provider::order_expiries(6).into_inner(), vec![0, 1, 2, 3]);
+		assert_eq!(Provider::order_expiries(7).into_inner(), vec![4]);
+
+		run_to_block(6);
+		assert_eq!(RentalOrders::<Test>::iter_keys().collect::<Vec<_>>(), vec![4]);
+		run_to_block(7);
+		assert_eq!(RentalOrders::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn settle_order_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 5);
+
+		assert_noop!(
+			Provider::settle_order(Origin::signed(CHARLIE), order_index),
+			Error::<Test>::OrderNotExpired
+		);
+		assert_noop!(
+			Provider::settle_order(Origin::signed(CHARLIE), 1),
+			Error::<Test>::OrderNotFound
+		);
+
+		// anyone can settle an expired order that was not processed in its block
+		OrderExpiries::<Test>::remove(6);
+		run_to_block(6);
+		assert!(Provider::rental_order(order_index).is_some());
+		assert_ok!(Provider::settle_order(Origin::signed(CHARLIE), order_index));
+		assert_eq!(Provider::rental_order(order_index), None);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 50);
+	});
+}
+
+#[test]
+fn renew_order_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 5);
+
+		assert_ok!(Provider::renew_order(Origin::signed(BOB), order_index, 5));
+		let order = Provider::rental_order(order_index).unwrap();
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(10, 10, 11));
+		assert_eq!(order.deposit, 100);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(Provider::order_expiries(11).into_inner(), vec![order_index]);
+		System::assert_last_event(Event::Provider(crate::Event::OrderRenewed {
+			order_index,
+			end_of_rent: 11,
+			deposit: 50,
+		}));
+
+		// the previous expiry is skipped
+		run_to_block(6);
+		assert!(Provider::rental_order(order_index).is_some());
+		run_to_block(11);
+		assert_eq!(Provider::rental_order(order_index), None);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 100);
+	});
+}
+
+#[test]
+fn renew_order_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 50);
+
+		assert_noop!(
+			Provider::renew_order(Origin::signed(BOB), order_index, 0),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			Provider::renew_order(Origin::signed(BOB), 1, 10),
+			Error::<Test>::OrderNotFound
+		);
+		assert_noop!(
+			Provider::renew_order(Origin::signed(CHARLIE), order_index, 10),
+			Error::<Test>::NotRenter
+		);
+		// the resource can be rented until block 101
+		assert_noop!(
+			Provider::renew_order(Origin::signed(BOB), order_index, 51),
+			Error::<Test>::ExceedsRentableDuration
+		);
+
+		fill_queues::<OrderExpiries<Test>, _>(61);
+		assert_noop!(
+			Provider::renew_order(Origin::signed(BOB), order_index, 10),
+			Error::<Test>::QueueFull
+		);
+
+		System::set_block_number(51);
+		assert_noop!(
+			Provider::renew_order(Origin::signed(BOB), order_index, 10),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+#[test]
+fn terminate_order_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 10);
+
+		run_to_block(5);
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), order_index));
+
+		// 4 blocks have been used
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 40);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 40);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Provider::rental_order(order_index), None);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(10, 6, 4)));
+		System::assert_last_event(Event::Provider(crate::Event::OrderTerminated {
+			order_index,
+			renter: BOB,
+			paid: 40,
+			refund: 60,
+		}));
+
+		// the expiry of the terminated order is ignored
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 40);
+	});
+}
+
+#[test]
+fn terminate_order_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let order_index = rent(BOB, index, 10);
+
+		assert_noop!(
+			Provider::terminate_order(Origin::signed(BOB), 1),
+			Error::<Test>::OrderNotFound
+		);
+		assert_noop!(
+			Provider::terminate_order(Origin::signed(CHARLIE), order_index),
+			Error::<Test>::NotRenter
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Provider::terminate_order(Origin::signed(BOB), order_index),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+fn reserve(who: AccountId, index: u64, start: BlockNumber, end: BlockNumber) -> u64 {
+	let reservation_index = Provider::reservation_index();
+	assert_ok!(Provider::reserve_resource(Origin::signed(who), index, start, end));
+	reservation_index
+}
+
+#[test]
+fn reserve_resource_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		// a lease ending when the reservation starts does not conflict with it
+		let order_index = rent(CHARLIE, index, 4);
+
+		let reservation_index = reserve(BOB, index, 5, 10);
+
+		// a fifth of the price of the 5 reserved blocks
+		assert_eq!(Balances::reserved_balance(BOB), 10);
+		assert_eq!(
+			Provider::reservation(reservation_index),
+			Some(Reservation::new(reservation_index, index, BOB, ALICE, 10, 5, 10, 10))
+		);
+		assert_eq!(Provider::reservation_index(), 1);
+		assert_eq!(Provider::resource_reservations(index).unwrap().into_inner(), vec![(5, 10)]);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceReserved {
+			renter: BOB,
+			resource_index: index,
+			reservation_index,
+			start: 5,
+			end: 10,
+			deposit: 10,
+		}));
+
+		// the reserved blocks can not be rented
+		assert_noop!(
+			Provider::renew_order(Origin::signed(CHARLIE), order_index, 1),
+			Error::<Test>::ResourceReserved
+		);
+
+		// the lease starts at the reserved unit price, after the ended lease is settled
+		assert_ok!(Provider::update_resource(
+			Origin::signed(ALICE),
+			index,
+			None,
+			None,
+			None,
+			Some(20)
+		));
+		run_to_block(5);
+		assert_eq!(Provider::rental_order(order_index), None);
+		let order = Provider::rental_order(1).unwrap();
+		assert_eq!(order.renter, BOB);
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(10, 5, 10));
+		assert_eq!(Balances::reserved_balance(BOB), 50);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Inuse);
+		assert_eq!(Provider::reservation(reservation_index), None);
+		assert_eq!(Provider::resource_reservations(index), None);
+		System::assert_has_event(Event::Provider(crate::Event::ReservationActivated {
+			reservation_index,
+			order_index: 1,
+		}));
+	});
+}
+
+#[test]
+fn reserve_resource_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let rented = register(ALICE, b"peer1");
+		rent(CHARLIE, rented, 10);
+
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), index, 1, 10),
+			Error::<Test>::InvalidReservation
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), index, 5, 5),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), 2, 5, 10),
+			Error::<Test>::ResourceNotFound
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(ALICE), index, 5, 10),
+			Error::<Test>::CannotRentOwnResource
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), index, 5, 200),
+			Error::<Test>::ExceedsRentableDuration
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), rented, 5, 20),
+			Error::<Test>::ResourceInUse
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(DAVE), index, 5, 10),
+			Error::<Test>::InsufficientBalance
+		);
+
+		reserve(BOB, index, 5, 10);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(CHARLIE), index, 8, 12),
+			Error::<Test>::ResourceReserved
+		);
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(CHARLIE), index, 5),
+			Error::<Test>::ResourceReserved
+		);
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(ALICE), index),
+			Error::<Test>::ResourceReserved
+		);
+		reserve(CHARLIE, index, 10, 12);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(CHARLIE), index, 12, 14),
+			Error::<Test>::TooManyReservations
+		);
+	});
+}
+
+#[test]
+fn rent_by_demand_skips_reserved_resources() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		reserve(BOB, index, 5, 10);
+		let demand =
+			ResourceDemand::new(2, 4, Specification::General, None, None, Default::default());
+
+		assert_noop!(
+			Provider::rent_by_demand(
+				Origin::signed(CHARLIE),
+				demand.clone(),
+				10,
+				10,
+				MatchPolicy::Cheapest,
+			),
+			Error::<Test>::NoMatchingResource
+		);
+		assert_ok!(Provider::rent_by_demand(
+			Origin::signed(CHARLIE),
+			demand,
+			10,
+			4,
+			MatchPolicy::Cheapest,
+		));
+	});
+}
+
+#[test]
+fn cancel_reservation_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let reservation_index = reserve(BOB, index, 5, 10);
+
+		assert_ok!(Provider::cancel_reservation(Origin::signed(BOB), reservation_index));
+
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Provider::reservation(reservation_index), None);
+		assert_eq!(Provider::resource_reservations(index), None);
+		System::assert_last_event(Event::Provider(crate::Event::ReservationCancelled {
+			reservation_index,
+			renter: BOB,
+		}));
+
+		// the activation is no longer scheduled
+		run_to_block(5);
+		assert_eq!(Provider::order_index(), 0);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+	});
+}
+
+#[test]
+fn cancel_reservation_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let reservation_index = reserve(BOB, index, 5, 10);
+
+		assert_noop!(
+			Provider::cancel_reservation(Origin::signed(BOB), 1),
+			Error::<Test>::ReservationNotFound
+		);
+		assert_noop!(
+			Provider::cancel_reservation(Origin::signed(CHARLIE), reservation_index),
+			Error::<Test>::NotRenter
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			Provider::cancel_reservation(Origin::signed(BOB), reservation_index),
+			Error::<Test>::ReservationStarted
+		);
+	});
+}
+
+#[test]
+fn activate_reservation_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let reservation_index = reserve(BOB, index, 5, 10);
+
+		assert_noop!(
+			Provider::activate_reservation(Origin::signed(BOB), reservation_index),
+			BadOrigin
+		);
+		assert_noop!(
+			Provider::activate_reservation(Origin::root(), 1),
+			Error::<Test>::ReservationNotFound
+		);
+		assert_noop!(
+			Provider::activate_reservation(Origin::root(), reservation_index),
+			Error::<Test>::ReservationNotStarted
+		);
+	});
+}
+
+#[test]
+fn unavailable_reserved_resources_refund_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let reservation_index = reserve(BOB, index, 5, 10);
+		Resources::<Test>::mutate(index, |r| r.as_mut().unwrap().status = ResourceStatus::Offline);
+
+		run_to_block(5);
+
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Provider::order_index(), 0);
+		assert_eq!(Provider::reservation(reservation_index), None);
+		System::assert_has_event(Event::Provider(crate::Event::ReservationFailed {
+			reservation_index,
+			renter: BOB,
+		}));
+	});
+}
+
+#[test]
+fn reservations_failing_for_another_reason_refund_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let index = register(CHARLIE, b"peer4");
+		let reservation_index = reserve(BOB, index, 5, 10);
+		// the renter has as many orders as it can have
+		for i in 0..4 {
+			let rented = register(ALICE, format!("peer{}", i).as_bytes());
+			rent(BOB, rented, 10);
+		}
+		let provider_balance = Balances::free_balance(CHARLIE);
+		let reserved = Balances::reserved_balance(BOB);
+
+		run_to_block(5);
+
+		assert_eq!(Balances::reserved_balance(BOB), reserved - 10);
+		assert_eq!(Balances::free_balance(CHARLIE), provider_balance);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+		assert_eq!(Provider::reservation(reservation_index), None);
+		System::assert_has_event(Event::Provider(crate::Event::ReservationFailed {
+			reservation_index,
+			renter: BOB,
+		}));
+	});
+}
+
+#[test]
+fn renters_not_showing_up_forfeit_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let reservation_index = reserve(BOB, index, 5, 10);
+		// the renter can no longer pay for the lease
+		assert_ok!(Balances::transfer(Origin::signed(BOB), CHARLIE, INITIAL_BALANCE - 11));
+		let provider_balance = Balances::free_balance(ALICE);
+
+		run_to_block(5);
+
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 1);
+		assert_eq!(Balances::free_balance(ALICE), provider_balance + 10);
+		assert_eq!(Provider::order_index(), 0);
+		assert_eq!(Provider::renter_orders(BOB), None);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+		assert_eq!(Provider::reservation(reservation_index), None);
+		System::assert_has_event(Event::Provider(crate::Event::ReservationForfeited {
+			reservation_index,
+			renter: BOB,
+			provider: ALICE,
+			amount: 10,
+		}));
+	});
+}
+
+fn list(who: AccountId, index: u64, kind: AuctionKind<BlockNumber>, close_at: BlockNumber) -> u64 {
+	let auction_index = Provider::auction_index();
+	assert_ok!(Provider::list_auction(Origin::signed(who), index, kind, 10, 10, close_at));
+	auction_index
+}
+
+/// commit a sealed bid of `unit_price`, hidden behind a salt derived from it
+fn commit(who: AccountId, auction_index: u64, unit_price: Balance, deposit: Balance) {
+	let commitment =
+		Provider::bid_commitment(&who, unit_price, &H256::repeat_byte(unit_price as u8));
+	assert_ok!(Provider::commit_bid(Origin::signed(who), auction_index, commitment, deposit));
+}
+
+fn reveal(who: AccountId, auction_index: u64, unit_price: Balance) -> DispatchResult {
+	Provider::reveal_bid(
+		Origin::signed(who),
+		auction_index,
+		unit_price,
+		H256::repeat_byte(unit_price as u8),
+	)
+}
+
+#[test]
+fn list_auction_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		let auction_index = list(ALICE, index, AuctionKind::English, 5);
+
+		assert_eq!(
+			Provider::auction(auction_index),
+			Some(Auction::new(auction_index, index, ALICE, AuctionKind::English, 10, 10, 5))
+		);
+		assert_eq!(Provider::auction_index(), 1);
+		assert_eq!(Provider::resource_auction(index), Some(auction_index));
+		System::assert_last_event(Event::Provider(crate::Event::AuctionListed {
+			resource_index: index,
+			auction_index,
+			kind: AuctionKind::English,
+			reserve_price: 10,
+			lease_duration: 10,
+			close_at: 5,
+		}));
+
+		// the resource can only be leased through the auction
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 5),
+			Error::<Test>::ResourceAuctioned
+		);
+		assert_noop!(
+			Provider::reserve_resource(Origin::signed(BOB), index, 20, 30),
+			Error::<Test>::ResourceAuctioned
+		);
+		assert_noop!(
+			Provider::remove_resource(Origin::signed(ALICE), index),
+			Error::<Test>::ResourceAuctioned
+		);
+	});
+}
+
+#[test]
+fn list_auction_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let rented = register(ALICE, b"peer1");
+		rent(CHARLIE, rented, 10);
+		let sealed = |reveal_start| AuctionKind::SealedBid { reveal_start };
+
+		assert_noop!(
+			Provider::list_auction(Origin::signed(BOB), index, AuctionKind::English, 10, 10, 5),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), rented, AuctionKind::English, 10, 10, 5),
+			Error::<Test>::ResourceInUse
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, AuctionKind::English, 10, 0, 5),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, AuctionKind::English, 10, 10, 1),
+			Error::<Test>::InvalidAuction
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, sealed(1), 10, 10, 5),
+			Error::<Test>::InvalidAuction
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, sealed(5), 10, 10, 5),
+			Error::<Test>::InvalidAuction
+		);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, AuctionKind::English, 10, 100, 5),
+			Error::<Test>::ExceedsRentableDuration
+		);
+
+		reserve(BOB, index, 10, 20);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, AuctionKind::English, 10, 10, 5),
+			Error::<Test>::ResourceReserved
+		);
+		assert_ok!(Provider::cancel_reservation(Origin::signed(BOB), 0));
+
+		list(ALICE, index, AuctionKind::English, 5);
+		assert_noop!(
+			Provider::list_auction(Origin::signed(ALICE), index, AuctionKind::English, 10, 10, 5),
+			Error::<Test>::ResourceAuctioned
+		);
+	});
+}
+
+#[test]
+fn english_auctions_lease_to_the_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let auction_index = list(ALICE, index, AuctionKind::English, 5);
+
+		assert_ok!(Provider::bid(Origin::signed(BOB), auction_index, 10));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(Provider::auction(auction_index).unwrap().best_bid, Some((BOB, 10)));
+		System::assert_last_event(Event::Provider(crate::Event::BidPlaced {
+			auction_index,
+			bidder: BOB,
+			unit_price: 10,
+			deposit: 100,
+		}));
+
+		// the outbid bidder is unreserved
+		assert_ok!(Provider::bid(Origin::signed(CHARLIE), auction_index, 12));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 120);
+		assert_eq!(Provider::auction_bid(auction_index, BOB), None);
+		System::assert_has_event(Event::Provider(crate::Event::BidUnreserved {
+			auction_index,
+			bidder: BOB,
+			amount: 100,
+		}));
+
+		assert_noop!(
+			Provider::close_auction(Origin::root(), auction_index),
+			Error::<Test>::AuctionNotClosed
+		);
+
+		// the winning bid becomes the unit price of the lease, from the close of the auction
+		run_to_block(5);
+		let order = Provider::rental_order(0).unwrap();
+		assert_eq!(order.renter, CHARLIE);
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(12, 10, 15));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 120);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Inuse);
+		assert_eq!(Provider::auction(auction_index), None);
+		assert_eq!(Provider::resource_auction(index), None);
+		assert_eq!(Provider::auction_bid(auction_index, CHARLIE), None);
+		System::assert_has_event(Event::Provider(crate::Event::AuctionWon {
+			auction_index,
+			winner: CHARLIE,
+			unit_price: 12,
+			order_index: 0,
+		}));
+	});
+}
+
+#[test]
+fn bid_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let auction_index = list(ALICE, index, AuctionKind::English, 5);
+		let sealed_index = register(ALICE, b"peer1");
+		let sealed = list(ALICE, sealed_index, AuctionKind::SealedBid { reveal_start: 3 }, 5);
+
+		assert_noop!(Provider::bid(Origin::signed(BOB), 2, 10), Error::<Test>::AuctionNotFound);
+		assert_noop!(
+			Provider::bid(Origin::signed(ALICE), auction_index, 10),
+			Error::<Test>::CannotRentOwnResource
+		);
+		assert_noop!(
+			Provider::bid(Origin::signed(BOB), sealed, 10),
+			Error::<Test>::WrongAuctionKind
+		);
+		assert_noop!(
+			Provider::bid(Origin::signed(BOB), auction_index, 9),
+			Error::<Test>::BidTooLow
+		);
+		assert_noop!(
+			Provider::bid(Origin::signed(DAVE), auction_index, 10),
+			Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Provider::bid(Origin::signed(BOB), auction_index, 10));
+		assert_noop!(
+			Provider::bid(Origin::signed(CHARLIE), auction_index, 10),
+			Error::<Test>::BidTooLow
+		);
+		assert_noop!(Provider::close_auction(Origin::signed(BOB), auction_index), BadOrigin);
+	});
+}
+
+#[test]
+fn sealed_bid_auctions_lease_to_the_highest_revealed_bid() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let english_index = register(ALICE, b"peer1");
+		let english = list(ALICE, english_index, AuctionKind::English, 5);
+		let auction_index = list(ALICE, index, AuctionKind::SealedBid { reveal_start: 4 }, 8);
+
+		commit(BOB, auction_index, 12, 150);
+		assert_eq!(Balances::reserved_balance(BOB), 150);
+		assert_eq!(Provider::auction(auction_index).unwrap().commitments, 1);
+		System::assert_last_event(Event::Provider(crate::Event::BidCommitted {
+			auction_index,
+			bidder: BOB,
+			deposit: 150,
+		}));
+		commit(CHARLIE, auction_index, 11, 110);
+
+		let commitment = Provider::bid_commitment(&DAVE, 10, &H256::zero());
+		assert_noop!(
+			Provider::commit_bid(Origin::signed(BOB), auction_index, commitment, 100),
+			Error::<Test>::AlreadyCommitted
+		);
+		assert_noop!(
+			Provider::commit_bid(Origin::signed(DAVE), auction_index, commitment, 100),
+			Error::<Test>::TooManyBids
+		);
+		assert_noop!(
+			Provider::commit_bid(Origin::signed(DAVE), english, commitment, 100),
+			Error::<Test>::WrongAuctionKind
+		);
+		assert_noop!(reveal(BOB, auction_index, 12), Error::<Test>::NotRevealPhase);
+
+		run_to_block(4);
+		assert_noop!(
+			Provider::commit_bid(Origin::signed(DAVE), auction_index, commitment, 100),
+			Error::<Test>::NotCommitPhase
+		);
+		assert_noop!(reveal(CHARLIE, auction_index, 12), Error::<Test>::InvalidReveal);
+		assert_noop!(reveal(DAVE, auction_index, 12), Error::<Test>::BidNotFound);
+
+		assert_ok!(reveal(CHARLIE, auction_index, 11));
+		assert_eq!(Provider::auction(auction_index).unwrap().best_bid, Some((CHARLIE, 11)));
+		System::assert_last_event(Event::Provider(crate::Event::BidRevealed {
+			auction_index,
+			bidder: CHARLIE,
+			unit_price: 11,
+		}));
+		assert_noop!(reveal(CHARLIE, auction_index, 11), Error::<Test>::AlreadyRevealed);
+
+		// the outbid bidder is unreserved
+		assert_ok!(reveal(BOB, auction_index, 12));
+		assert_eq!(Provider::auction(auction_index).unwrap().best_bid, Some((BOB, 12)));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		System::assert_last_event(Event::Provider(crate::Event::BidUnreserved {
+			auction_index,
+			bidder: CHARLIE,
+			amount: 110,
+		}));
+
+		// the deposit above the price of the lease is unreserved
+		run_to_block(8);
+		let order = Provider::rental_order(0).unwrap();
+		assert_eq!(order.renter, BOB);
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(12, 10, 18));
+		assert_eq!(Balances::reserved_balance(BOB), 120);
+		assert_eq!(Provider::auction(auction_index), None);
+		System::assert_has_event(Event::Provider(crate::Event::AuctionWon {
+			auction_index,
+			winner: BOB,
+			unit_price: 12,
+			order_index: 0,
+		}));
+	});
+}
+
+#[test]
+fn auctions_without_winner_unreserve_the_bids() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let auction_index = list(ALICE, index, AuctionKind::SealedBid { reveal_start: 4 }, 8);
+		commit(BOB, auction_index, 12, 150);
+		commit(CHARLIE, auction_index, 9, 100);
+
+		run_to_block(4);
+		assert_noop!(reveal(CHARLIE, auction_index, 9), Error::<Test>::BidTooLow);
+
+		// the unrevealed bids are unreserved when the auction closes
+		run_to_block(8);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Provider::auction_bid(auction_index, BOB), None);
+		assert_eq!(Provider::order_index(), 0);
+		assert_eq!(Provider::resource_auction(index), None);
+		System::assert_has_event(Event::Provider(crate::Event::BidUnreserved {
+			auction_index,
+			bidder: BOB,
+			amount: 150,
+		}));
+		System::assert_has_event(Event::Provider(crate::Event::AuctionClosed { auction_index }));
+
+		// the resource can be rented again
+		rent(BOB, index, 1);
+	});
+}
+
+#[test]
+fn revealed_bids_must_be_covered_by_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let auction_index = list(ALICE, index, AuctionKind::SealedBid { reveal_start: 4 }, 8);
+		commit(BOB, auction_index, 12, 110);
+
+		run_to_block(4);
+		assert_noop!(reveal(BOB, auction_index, 12), Error::<Test>::BidExceedsDeposit);
+	});
+}
+
+#[test]
+fn offline_auctioned_resources_unreserve_the_winner() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let auction_index = list(ALICE, index, AuctionKind::English, 12);
+		assert_ok!(Provider::bid(Origin::signed(BOB), auction_index, 10));
+
+		// the resource misses its heartbeats and goes offline at block 11
+		run_to_block(12);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Provider::order_index(), 0);
+		System::assert_has_event(Event::Provider(crate::Event::BidUnreserved {
+			auction_index,
+			bidder: BOB,
+			amount: 100,
+		}));
+		System::assert_has_event(Event::Provider(crate::Event::AuctionClosed { auction_index }));
+	});
+}
+
+/// market of a class with `leased` billionths of its resources allocated to leases
+fn market(resources: u32, leased: u64, multiplier: FixedU128) -> ClassMarket {
+	ClassMarket { resources, leased, multiplier }
+}
+
+#[test]
+fn class_markets_count_registered_and_leased_resources() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let other = register(ALICE, b"peer1");
+		// 3 cpus and 5 memory round down to the class of 2 cpus and 4 memory
+		let rounded = register_with(ALICE, b"peer2", mock::config(3, 5), 10);
+		let class = Provider::resource(index).unwrap().class();
+		assert_eq!(class, ResourceClass::new(Specification::General, 2, 4));
+		assert_eq!(Provider::resource(rounded).unwrap().class(), class);
+		assert_eq!(Provider::class_market(&class), Some(market(3, 0, FixedU128::one())));
+
+		let order_index = rent(BOB, index, 5);
+		assert_eq!(
+			Provider::class_market(&class),
+			Some(market(3, 1_000_000_000, FixedU128::one()))
+		);
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), order_index));
+		assert_eq!(Provider::class_market(&class), Some(market(3, 0, FixedU128::one())));
+
+		// a resource changing class moves to the market of its new class
+		assert_ok!(Provider::update_resource(
+			Origin::signed(ALICE),
+			other,
+			None,
+			Some(mock::config(8, 16)),
+			None,
+			None
+		));
+		let new_class = ResourceClass::new(Specification::General, 8, 16);
+		assert_eq!(Provider::class_market(&class), Some(market(2, 0, FixedU128::one())));
+		assert_eq!(Provider::class_market(&new_class), Some(market(1, 0, FixedU128::one())));
+
+		// markets without resources are removed
+		assert_ok!(Provider::remove_resource(Origin::signed(ALICE), other));
+		assert_eq!(Provider::class_market(&new_class), None);
+	});
+}
+
+#[test]
+fn class_markets_count_the_allocated_share_of_divisible_resources() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let class = Provider::resource(index).unwrap().class();
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+
+		// a slice of 1 of the 2 cpus and 1 of the 4 memory is half of the resource
+		let first = rent_slice(BOB, index, 1, 1, 10);
+		assert_eq!(Provider::class_market(&class), Some(market(1, 500_000_000, FixedU128::one())));
+		let second = rent_slice(CHARLIE, index, 1, 3, 10);
+		assert_eq!(
+			Provider::class_market(&class),
+			Some(market(1, 1_000_000_000, FixedU128::one()))
+		);
+
+		assert_ok!(Provider::terminate_order(Origin::signed(CHARLIE), second));
+		assert_eq!(Provider::class_market(&class), Some(market(1, 500_000_000, FixedU128::one())));
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), first));
+		assert_eq!(Provider::class_market(&class), Some(market(1, 0, FixedU128::one())));
+	});
+}
+
+#[test]
+fn set_dynamic_pricing_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::set_dynamic_pricing(Origin::signed(BOB), index, Some((5, 20))),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Provider::set_dynamic_pricing(Origin::signed(ALICE), index, Some((20, 5))),
+			Error::<Test>::InvalidPriceBounds
+		);
+
+		assert_ok!(Provider::set_dynamic_pricing(Origin::signed(ALICE), index, Some((5, 20))));
+		assert_eq!(Provider::price_bounds(index), Some(PriceBounds::new(5, 20)));
+		System::assert_last_event(Event::Provider(crate::Event::DynamicPricingSet {
+			index,
+			bounds: Some(PriceBounds::new(5, 20)),
+		}));
+
+		assert_ok!(Provider::set_dynamic_pricing(Origin::signed(ALICE), index, None));
+		assert_eq!(Provider::price_bounds(index), None);
+		System::assert_last_event(Event::Provider(crate::Event::DynamicPricingSet {
+			index,
+			bounds: None,
+		}));
+	});
+}
+
+#[test]
+fn dynamic_prices_follow_the_utilization_of_their_class() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		let leased = register(ALICE, b"peer1");
+		let idle = register_with(ALICE, b"peer2", mock::config(8, 16), 10);
+		assert_ok!(Provider::set_dynamic_pricing(Origin::signed(ALICE), index, Some((5, 30))));
+		assert_ok!(Provider::set_dynamic_pricing(Origin::signed(ALICE), idle, Some((9, 30))));
+		rent(BOB, leased, 10);
+		let class = Provider::resource(index).unwrap().class();
+		let idle_class = Provider::resource(idle).unwrap().class();
+
+		// half of the class is leased, above the target of a quarter, a single class is adjusted
+		// per block
+		run_to_block(2);
+		assert_eq!(Provider::class_market(&idle_class).unwrap().multiplier, FixedU128::one());
+		run_to_block(3);
+		assert_eq!(
+			Provider::class_market(&class).unwrap().multiplier,
+			FixedU128::saturating_from_rational(145, 128)
+		);
+		assert_eq!(Provider::unit_price_quote(index), Some(11));
+		assert_eq!(Provider::rental_quote(index, 10), Some(110));
+		// resources not opted in keep their unit price
+		assert_eq!(Provider::unit_price_quote(leased), Some(10));
+		// nothing of the other class is leased, its price goes down to the floor
+		assert_eq!(
+			Provider::class_market(&idle_class).unwrap().multiplier,
+			FixedU128::saturating_from_rational(113, 128)
+		);
+		assert_eq!(Provider::unit_price_quote(idle), Some(9));
+
+		// renters pay the quoted unit price
+		let order_index = rent(CHARLIE, index, 5);
+		assert_eq!(Provider::rental_order(order_index).unwrap().rental_info.rent_unit_price, 11);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 55);
+
+		// multipliers do not go below the minimum
+		run_to_block(13);
+		assert_eq!(
+			Provider::class_market(&idle_class).unwrap().multiplier,
+			FixedU128::saturating_from_rational(1, 2)
+		);
+	});
+}
+
+fn asset_balance(who: &AccountId) -> AssetBalance {
+	Assets::balance(ASSET, who)
+}
+
+/// rent a resource paying in `ASSET`
+fn rent_in_asset(who: AccountId, index: u64, duration: BlockNumber) -> u64 {
+	let order_index = Provider::order_index();
+	assert_ok!(Provider::rent_resource_in_asset(Origin::signed(who), index, ASSET, duration));
+	order_index
+}
+
+/// unit prices of a resource in assets
+fn prices(
+	prices: Vec<(u32, AssetBalance)>,
+) -> BoundedVec<(u32, AssetBalance), <Test as Config>::MaxAcceptedAssets> {
+	prices.try_into().unwrap()
+}
+
+/// register a resource that can be paid 3 per block in `ASSET`
+fn register_for_asset(who: AccountId, peer_id: &[u8]) -> u64 {
+	let index = register(who.clone(), peer_id);
+	assert_ok!(Provider::set_asset_prices(Origin::signed(who), index, prices(vec![(ASSET, 3)])));
+	index
+}
+
+#[test]
+fn set_asset_prices_works() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::set_asset_prices(Origin::signed(BOB), index, prices(vec![(ASSET, 3)])),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Provider::set_asset_prices(
+				Origin::signed(ALICE),
+				index,
+				prices(vec![(ASSET, 3), (ASSET, 4)])
+			),
+			Error::<Test>::DuplicateAsset
+		);
+
+		assert_ok!(Provider::set_asset_prices(
+			Origin::signed(ALICE),
+			index,
+			prices(vec![(ASSET, 3), (UNKNOWN_ASSET, 4)])
+		));
+		assert_eq!(
+			Provider::asset_prices(index).into_inner(),
+			vec![(ASSET, 3), (UNKNOWN_ASSET, 4)]
+		);
+		System::assert_last_event(Event::Provider(crate::Event::AssetPricesSet {
+			index,
+			prices: vec![(ASSET, 3), (UNKNOWN_ASSET, 4)],
+		}));
+
+		// the prices are replaced
+		assert_ok!(Provider::set_asset_prices(Origin::signed(ALICE), index, prices(vec![])));
+		assert!(!AssetPrices::<Test>::contains_key(index));
+
+		// and removed with the resource
+		assert_ok!(Provider::set_asset_prices(
+			Origin::signed(ALICE),
+			index,
+			prices(vec![(ASSET, 3)])
+		));
+		assert_ok!(Provider::remove_resource(Origin::signed(ALICE), index));
+		assert!(!AssetPrices::<Test>::contains_key(index));
+	});
+}
+
+#[test]
+fn rent_resource_in_asset_works() {
+	new_test_ext().execute_with(|| {
+		let index = register_for_asset(ALICE, b"peer0");
+		let escrow = Provider::escrow_account();
+
+		let order_index = rent_in_asset(BOB, index, 10);
+		assert_eq!(Provider::order_asset(order_index), Some(ASSET));
+		let order = Provider::rental_order(order_index).unwrap();
+		assert_eq!(order.rental_info, ResourceRentalInfo::new(3, 10, 11));
+		assert_eq!(order.deposit, 30);
+		// the first deposit also pays the minimum balance of 1 the escrow account keeps
+		assert_eq!(asset_balance(&BOB), INITIAL_BALANCE as AssetBalance - 31);
+		assert_eq!(asset_balance(&escrow), 31);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Inuse);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceRented {
+			renter: BOB,
+			resource_index: index,
+			order_index,
+			slice: ResourceSlice::new(2, 4),
+			end_of_rent: 11,
+			deposit: 30,
+			asset_id: Some(ASSET),
+		}));
+
+		// renewals are escrowed in the asset of the order
+		assert_ok!(Provider::renew_order(Origin::signed(BOB), order_index, 5));
+		assert_eq!(asset_balance(&BOB), INITIAL_BALANCE as AssetBalance - 46);
+		assert_eq!(asset_balance(&escrow), 46);
+
+		// and the provider is paid in it
+		run_to_block(10);
+		assert_ok!(heartbeat(&ALICE, index, 10));
+		run_to_block(16);
+		assert!(Provider::rental_order(order_index).is_none());
+		assert_eq!(Provider::order_asset(order_index), None);
+		assert_eq!(asset_balance(&ALICE), INITIAL_BALANCE as AssetBalance + 45);
+		assert_eq!(asset_balance(&escrow), 1);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn rent_resource_in_asset_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register_for_asset(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::rent_resource_in_asset(Origin::signed(BOB), index, UNKNOWN_ASSET, 10),
+			Error::<Test>::AssetNotAccepted
+		);
+		assert_noop!(
+			Provider::rent_resource_in_asset(Origin::signed(DAVE), index, ASSET, 10),
+			Error::<Test>::InsufficientBalance
+		);
+
+		// a partly leased resource can only be leased in slices
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		rent_slice(CHARLIE, index, 1, 2, 10);
+		assert_noop!(
+			Provider::rent_resource_in_asset(Origin::signed(BOB), index, ASSET, 10),
+			Error::<Test>::ResourceNotAvailable
+		);
+	});
+}
+
+#[test]
+fn terminated_asset_orders_are_refunded_in_the_asset() {
+	new_test_ext().execute_with(|| {
+		let index = register_for_asset(ALICE, b"peer0");
+		let order_index = rent_in_asset(BOB, index, 10);
+
+		run_to_block(5);
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), order_index));
+		assert_eq!(asset_balance(&ALICE), INITIAL_BALANCE as AssetBalance + 12);
+		assert_eq!(asset_balance(&BOB), INITIAL_BALANCE as AssetBalance - 13);
+		assert_eq!(asset_balance(&Provider::escrow_account()), 1);
+		System::assert_last_event(Event::Provider(crate::Event::OrderTerminated {
+			order_index,
+			renter: BOB,
+			paid: 12,
+			refund: 18,
+		}));
+	});
+}
+
+#[test]
+fn offline_resources_leased_in_assets_are_slashed_in_the_asset() {
+	new_test_ext().execute_with(|| {
+		let index = register_for_asset(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		let order_index = rent_in_asset(BOB, index, 12);
+
+		// half of the deposit is refunded to the renter, which gets its share of the bond too
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+		assert_eq!(Provider::rental_order(order_index).unwrap().deposit, 18);
+		assert_eq!(asset_balance(&BOB), INITIAL_BALANCE as AssetBalance - 19);
+		System::assert_has_event(Event::Provider(crate::Event::AssetSlashed {
+			index,
+			renter: BOB,
+			asset_id: ASSET,
+			amount: 18,
+		}));
+		assert_eq!(Provider::resource_bond(index), Some(12));
+		assert_eq!(Balances::reserved_balance(ALICE), 12);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 12);
+		System::assert_last_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: BOB,
+			amount: 12,
+		}));
+
+		// the provider is paid what is left of the deposit
+		assert_ok!(heartbeat(&ALICE, index, 11));
+		run_to_block(13);
+		assert!(Provider::rental_order(order_index).is_none());
+		assert_eq!(asset_balance(&ALICE), INITIAL_BALANCE as AssetBalance + 18);
+		assert_eq!(asset_balance(&Provider::escrow_account()), 1);
+	});
+}
+
+#[test]
+fn offline_resources_leased_in_assets_are_slashed_without_a_bond() {
+	new_test_ext().execute_with(|| {
+		let index = register_for_asset(ALICE, b"peer0");
+		let order_index = rent_in_asset(BOB, index, 12);
+		assert_eq!(Provider::resource_bond(index), None);
+
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+		assert_eq!(Provider::rental_order(order_index).unwrap().deposit, 18);
+		System::assert_last_event(Event::Provider(crate::Event::AssetSlashed {
+			index,
+			renter: BOB,
+			asset_id: ASSET,
+			amount: 18,
+		}));
+	});
+}
+
+#[test]
+fn paying_a_deposit_out_keeps_the_other_deposits_escrowed() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::tokens::fungibles::{Create, Mutate};
+		// an asset with a minimum balance of 10
+		let asset = 9;
+		assert_ok!(<Assets as Create<AccountId>>::create(asset, ALICE, false, 10));
+		for who in [ALICE, BOB, CHARLIE] {
+			assert_ok!(<Assets as Mutate<AccountId>>::mint_into(asset, &who, 100));
+		}
+		let index = register(ALICE, b"peer0");
+		let other = register(ALICE, b"peer1");
+		for i in [index, other] {
+			assert_ok!(Provider::set_asset_prices(
+				Origin::signed(ALICE),
+				i,
+				prices(vec![(asset, 3)])
+			));
+		}
+		let escrow = Provider::escrow_account();
+
+		assert_ok!(Provider::rent_resource_in_asset(Origin::signed(BOB), index, asset, 10));
+		let order_index = Provider::order_index();
+		assert_ok!(Provider::rent_resource_in_asset(Origin::signed(CHARLIE), other, asset, 3));
+		assert_eq!(Assets::balance(asset, &escrow), 49);
+
+		// paying the first deposit leaves less than the minimum balance of the asset escrowed
+		// for the second order, which is kept
+		assert_ok!(Provider::terminate_order(Origin::signed(BOB), order_index - 1));
+		assert_eq!(Assets::balance(asset, &escrow), 19);
+		assert_eq!(Provider::rental_order(order_index).unwrap().deposit, 9);
+
+		run_to_block(4);
+		assert!(Provider::rental_order(order_index).is_none());
+		assert_eq!(Assets::balance(asset, &ALICE), 109);
+		assert_eq!(Assets::balance(asset, &escrow), 10);
+	});
+}
+
+#[test]
+fn payments_that_can_not_be_transferred_can_be_claimed() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::tokens::fungibles::{Create, Mutate};
+		// an asset with a minimum balance of 10 the provider does not hold
+		let asset = 9;
+		assert_ok!(<Assets as Create<AccountId>>::create(asset, BOB, false, 10));
+		assert_ok!(<Assets as Mutate<AccountId>>::mint_into(asset, &BOB, 100));
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::set_asset_prices(
+			Origin::signed(ALICE),
+			index,
+			prices(vec![(asset, 3)])
+		));
+		assert_ok!(Provider::rent_resource_in_asset(Origin::signed(BOB), index, asset, 3));
+		let escrow = Provider::escrow_account();
+
+		// the payment is below the minimum balance of the provider account
+		run_to_block(4);
+		assert_eq!(Assets::balance(asset, &ALICE), 0);
+		assert_eq!(Assets::balance(asset, &escrow), 19);
+		assert_eq!(Provider::unclaimed_assets(ALICE, asset), 9);
+		System::assert_has_event(Event::Provider(crate::Event::AssetsUnclaimed {
+			who: ALICE,
+			asset_id: asset,
+			amount: 9,
+		}));
+
+		assert_noop!(
+			Provider::claim_assets(Origin::signed(BOB), asset),
+			Error::<Test>::NoUnclaimedAssets
+		);
+		assert_noop!(
+			Provider::claim_assets(Origin::signed(ALICE), asset),
+			TokenError::BelowMinimum
+		);
+
+		assert_ok!(<Assets as Mutate<AccountId>>::mint_into(asset, &ALICE, 10));
+		assert_ok!(Provider::claim_assets(Origin::signed(ALICE), asset));
+		assert_eq!(Assets::balance(asset, &ALICE), 19);
+		assert_eq!(Assets::balance(asset, &escrow), 10);
+		assert_eq!(Provider::unclaimed_assets(ALICE, asset), 0);
+		System::assert_last_event(Event::Provider(crate::Event::AssetsClaimed {
+			who: ALICE,
+			asset_id: asset,
+			amount: 9,
+		}));
+	});
+}
+
+#[test]
+fn heartbeats_keep_resources_online() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		run_to_block(6);
+		assert_ok!(heartbeat(&ALICE, index, 6));
+		assert_eq!(Provider::resource_heartbeat(index), Some(6));
+		// the deadline of the previous heartbeat is moved after the new one
+		assert!(heartbeat_deadlines(11).is_empty());
+		assert_eq!(heartbeat_deadlines(16), vec![index]);
+		System::assert_last_event(Event::Provider(crate::Event::HeartbeatReceived { index }));
+
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+		run_to_block(16);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+	});
+}
+
+#[test]
+fn resources_missing_heartbeats_go_offline_and_come_back() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		run_to_block(11);
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.status, ResourceStatus::Offline);
+		assert_eq!(resource.rental_statistics.fault_count, 1);
+		assert_eq!(resource.rental_statistics.fault_duration, 10);
+		assert_eq!(Provider::provider_points(ALICE), None);
+		assert_eq!(Provider::total_resource_points(), 0);
+		System::assert_last_event(Event::Provider(crate::Event::ResourceOffline { index }));
+
+		// offline resources can not be rented
+		assert_noop!(
+			Provider::rent_resource(Origin::signed(BOB), index, 10),
+			Error::<Test>::ResourceNotAvailable
+		);
+
+		// an offline resource can send a heartbeat at any time
+		run_to_block(15);
+		assert_ok!(heartbeat(&ALICE, index, 15));
+		let resource = Provider::resource(index).unwrap();
+		assert_eq!(resource.status, ResourceStatus::Unused);
+		assert_eq!(resource.rental_statistics.fault_duration, 14);
+		assert_eq!(Provider::provider_points(ALICE), Some(ProviderPoints::new(6, 6, 0)));
+		assert!(provider_events()
+			.contains(&crate::Event::ResourceOnline { index, status: ResourceStatus::Unused }));
+	});
+}
+
+#[test]
+fn offline_rented_resources_are_slashed() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		let order_index = rent(BOB, index, 50);
+
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+		assert_eq!(Provider::resource_bond(index), Some(12));
+		assert_eq!(Balances::reserved_balance(ALICE), 12);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 500 + 12);
+		System::assert_last_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: BOB,
+			amount: 12,
+		}));
+
+		// the lease goes on once the resource is back
+		assert_ok!(heartbeat(&ALICE, index, 11));
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Inuse);
+		assert!(Provider::rental_order(order_index).is_some());
+	});
+}
+
+#[test]
+fn offline_slashes_are_shared_by_the_renters_of_slices() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		rent_slice(BOB, index, 1, 2, 50);
+		rent_slice(CHARLIE, index, 1, 1, 50);
+
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+		assert_eq!(Provider::resource_bond(index), Some(12));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 250 + 6);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 250 + 6);
+		System::assert_has_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: BOB,
+			amount: 6,
+		}));
+		System::assert_last_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: CHARLIE,
+			amount: 6,
+		}));
+
+		// the resource is not fully allocated once it is back
+		assert_ok!(heartbeat(&ALICE, index, 11));
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Unused);
+	});
+}
+
+#[test]
+fn offline_slashes_of_overlapping_slices_add_up_to_the_slash() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		assert_ok!(Provider::bond_resource(Origin::signed(ALICE), index));
+		assert_ok!(Provider::set_resource_divisible(Origin::signed(ALICE), index, true));
+		// shares of 75% and 50% of the resource, scaled down to 60% and 40% of the slash of 12
+		rent_slice(BOB, index, 1, 3, 50);
+		rent_slice(CHARLIE, index, 1, 1, 50);
+
+		run_to_block(11);
+		System::assert_has_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: BOB,
+			amount: 7,
+		}));
+		System::assert_last_event(Event::Provider(crate::Event::BondSlashed {
+			index,
+			renter: CHARLIE,
+			amount: 4,
+		}));
+		assert_eq!(Provider::resource_bond(index), Some(13));
+		assert_eq!(Balances::reserved_balance(ALICE), 13);
+	});
+}
+
+#[test]
+fn heartbeat_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::heartbeat(
+				Origin::signed(ALICE),
+				HeartbeatPayload { block_number: 1, resource_index: index, public: public(&ALICE) },
+				MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+			),
+			BadOrigin
+		);
+		assert_noop!(heartbeat(&ALICE, index, 1), Error::<Test>::HeartbeatTooEarly);
+
+		run_to_block(6);
+		assert_noop!(heartbeat(&ALICE, 1, 6), Error::<Test>::ResourceNotFound);
+		assert_noop!(heartbeat(&BOB, index, 6), Error::<Test>::NotOwner);
+		assert_noop!(heartbeat(&ALICE, index, 1), Error::<Test>::StaleHeartbeat);
+	});
+}
+
+#[test]
+fn heartbeat_signature_is_verified() {
+	new_test_ext().execute_with(|| {
+		let key =
+			crypto::Public::generate_pair(Some(format!("0x{}", "05".repeat(32)).into_bytes()));
+		let public: MultiSigner = sr25519::Public::from(key).into();
+		let who = public.clone().into_account();
+		let index = register(who.clone(), b"peer0");
+		let validate = |heartbeat: HeartbeatPayload<MultiSigner, BlockNumber>,
+		                signature: MultiSignature| {
+			Provider::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::heartbeat { heartbeat, signature },
+			)
+		};
+		let sign = |heartbeat: &HeartbeatPayload<MultiSigner, BlockNumber>| {
+			SignedPayload::<Test>::sign::<crypto::ProviderAuthId>(heartbeat).unwrap()
+		};
+		let payload = |block_number| HeartbeatPayload {
+			block_number,
+			resource_index: index,
+			public: public.clone(),
+		};
+
+		// too early
+		assert_eq!(validate(payload(1), sign(&payload(1))), InvalidTransaction::Stale.into());
+
+		run_to_block(6);
+		assert_ok!(validate(payload(6), sign(&payload(6))));
+		assert_eq!(validate(payload(6), sign(&payload(5))), InvalidTransaction::BadProof.into());
+		assert_eq!(validate(payload(7), sign(&payload(7))), InvalidTransaction::Future.into());
+		assert_eq!(validate(payload(1), sign(&payload(1))), InvalidTransaction::Stale.into());
+
+		// heartbeats of others are rejected
+		let other = register(BOB, b"peer1");
+		let heartbeat = HeartbeatPayload { resource_index: other, ..payload(6) };
+		assert_eq!(
+			validate(heartbeat.clone(), sign(&heartbeat)),
+			InvalidTransaction::BadProof.into()
+		);
+	});
+}
+
+#[test]
+fn attested_scores_bound_the_specification() {
+	ExtBuilder::default()
+		.specification_thresholds(thresholds())
+		.build_and_execute(|| {
+			let index = Provider::resource_index();
+			assert_ok!(Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(4, 8),
+				scores(100),
+				10,
+				100,
+			));
+			assert_ok!(attest(&ALICE, index, 1, scores(50)));
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::Enhanced);
+
+			// declared scores can not go back above the attested ones
+			assert_noop!(
+				Provider::update_resource(
+					Origin::signed(ALICE),
+					index,
+					None,
+					None,
+					Some(scores(100)),
+					None
+				),
+				Error::<Test>::ScoresAboveAttestation
+			);
+			assert_ok!(Provider::update_resource(
+				Origin::signed(ALICE),
+				index,
+				None,
+				None,
+				Some(scores(50)),
+				None
+			));
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::Enhanced);
+
+			// nor does a refresh upgrade the resource past its attested scores, whatever it
+			// declared before its attestation
+			ResourceScores::<Test>::insert(index, scores(100));
+			assert_ok!(Provider::refresh_specification(Origin::signed(BOB), index));
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::Enhanced);
+		});
+}
+
+#[test]
+fn attest_hardware_works() {
+	ExtBuilder::default()
+		.specification_thresholds(thresholds())
+		.build_and_execute(|| {
+			let index = Provider::resource_index();
+			assert_ok!(Provider::register_resource(
+				Origin::signed(ALICE),
+				bounded(b"peer0"),
+				bounded(b"127.0.0.1"),
+				mock::config(4, 8),
+				scores(100),
+				10,
+				100,
+			));
+			assert_eq!(Provider::total_resource_points(), 48);
+
+			Now::set(12_000);
+			assert_ok!(attest(&ALICE, index, 1, scores(50)));
+			assert_eq!(
+				Provider::resource_attestation(index),
+				Some(HardwareAttestation {
+					scores: scores(50),
+					block_number: 1,
+					timestamp: 12_000
+				})
+			);
+			// downgraded to `Enhanced`, (4 + 8) * 2
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::Enhanced);
+			assert_eq!(Provider::total_resource_points(), 24);
+			assert!(provider_events().contains(&crate::Event::ResourceSpecificationChanged {
+				index,
+				specification: Specification::Enhanced,
+			}));
+			System::assert_last_event(Event::Provider(crate::Event::HardwareAttested {
+				index,
+				scores: scores(50),
+			}));
+
+			// flagged resources can not be rented nor matched
+			run_to_block(6);
+			assert_ok!(heartbeat(&ALICE, index, 6));
+			run_to_block(11);
+			assert_ok!(attest(&ALICE, index, 11, scores(5)));
+			assert_eq!(Provider::flagged_since(index), Some(11));
+			assert!(provider_events().contains(&crate::Event::ResourceFlagged { index }));
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::General);
+			assert_noop!(
+				Provider::rent_resource(Origin::signed(BOB), index, 10),
+				Error::<Test>::ResourceFlagged
+			);
+			let demand =
+				ResourceDemand::new(1, 1, Specification::General, None, None, Default::default());
+			assert_noop!(
+				Provider::rent_by_demand(
+					Origin::signed(BOB),
+					demand,
+					10,
+					10,
+					MatchPolicy::Cheapest
+				),
+				Error::<Test>::NoMatchingResource
+			);
+
+			// a later attestation unflags it without upgrading it
+			run_to_block(21);
+			assert_ok!(attest(&ALICE, index, 21, scores(100)));
+			assert_eq!(Provider::flagged_since(index), None);
+			assert!(provider_events().contains(&crate::Event::ResourceUnflagged { index }));
+			assert_eq!(Provider::resource(index).unwrap().specification, Specification::General);
+
+			assert_ok!(Provider::remove_resource(Origin::signed(ALICE), index));
+			assert_eq!(Provider::resource_attestation(index), None);
+		});
+}
+
+#[test]
+fn attest_hardware_fails() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::attest_hardware(
+				Origin::signed(ALICE),
+				AttestationPayload {
+					block_number: 1,
+					resource_index: index,
+					scores: scores(50),
+					public: public(&ALICE),
+				},
+				MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+			),
+			BadOrigin
+		);
+		assert_noop!(attest(&ALICE, 1, 1, scores(50)), Error::<Test>::ResourceNotFound);
+		assert_noop!(attest(&BOB, index, 1, scores(50)), Error::<Test>::NotOwner);
+
+		assert_ok!(attest(&ALICE, index, 1, scores(50)));
+		run_to_block(10);
+		assert_noop!(attest(&ALICE, index, 10, scores(50)), Error::<Test>::AttestationTooEarly);
+		run_to_block(11);
+		assert_noop!(attest(&ALICE, index, 1, scores(50)), Error::<Test>::StaleAttestation);
+		assert_ok!(attest(&ALICE, index, 11, scores(50)));
+	});
+}
+
+#[test]
+fn attestation_signature_is_verified() {
+	new_test_ext().execute_with(|| {
+		let key =
+			crypto::Public::generate_pair(Some(format!("0x{}", "05".repeat(32)).into_bytes()));
+		let public: MultiSigner = sr25519::Public::from(key).into();
+		let index = register(public.clone().into_account(), b"peer0");
+		let validate = |attestation: AttestationPayload<MultiSigner, BlockNumber>,
+		                signature: MultiSignature| {
+			Provider::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::attest_hardware { attestation, signature },
+			)
+		};
+		let sign = |attestation: &AttestationPayload<MultiSigner, BlockNumber>| {
+			SignedPayload::<Test>::sign::<crypto::ProviderAuthId>(attestation).unwrap()
+		};
+		let payload = |block_number, score| AttestationPayload {
+			block_number,
+			resource_index: index,
+			scores: scores(score),
+			public: public.clone(),
+		};
+
+		run_to_block(3);
+		assert_ok!(validate(payload(3, 50), sign(&payload(3, 50))));
+		assert_eq!(
+			validate(payload(3, 100), sign(&payload(3, 50))),
+			InvalidTransaction::BadProof.into()
+		);
+		assert_eq!(
+			validate(payload(4, 50), sign(&payload(4, 50))),
+			InvalidTransaction::Future.into()
+		);
+
+		// too early after the previous attestation
+		assert_ok!(Provider::attest_hardware(
+			Origin::none(),
+			payload(3, 50),
+			sign(&payload(3, 50))
+		));
+		run_to_block(12);
+		assert_eq!(
+			validate(payload(12, 50), sign(&payload(12, 50))),
+			InvalidTransaction::Stale.into()
+		);
+		run_to_block(13);
+		assert_eq!(
+			validate(payload(3, 50), sign(&payload(3, 50))),
+			InvalidTransaction::Stale.into()
+		);
+		assert_ok!(validate(payload(13, 50), sign(&payload(13, 50))));
+	});
+}
+
+#[test]
+fn audits_challenge_a_sample_of_resources() {
+	new_test_ext().execute_with(|| {
+		AuditPeriod::set(5);
+		register(ALICE, b"peer0");
+		register(BOB, b"peer1");
+		register(CHARLIE, b"peer2");
+
+		run_to_block(4);
+		assert_eq!(AuditChallenges::<Test>::iter().count(), 0);
+
+		run_to_block(5);
+		let challenged = Provider::challenge_deadlines(8).into_inner();
+		assert_eq!(challenged.len(), 2);
+		let nonce = |index| Provider::audit_challenge(index).unwrap().nonce;
+		for &index in &challenged {
+			assert_eq!(Provider::audit_challenge(index).unwrap().deadline, 8);
+			assert!(provider_events()
+				.contains(&crate::Event::ResourceChallenged { index, deadline: 8 }));
+		}
+		assert_ne!(nonce(challenged[0]), nonce(challenged[1]));
+
+		// only unanswered challenges are faults
+		let (answered, missed) = (challenged[0], challenged[1]);
+		let owner = Provider::resource(answered).unwrap().account_id;
+		assert_ok!(answer(&owner, answered, nonce(answered)));
+		assert_eq!(Provider::audit_challenge(answered), None);
+		System::assert_last_event(Event::Provider(crate::Event::ChallengeAnswered {
+			index: answered,
+		}));
+
+		run_to_block(8);
+		assert_eq!(Provider::audit_challenge(missed), None);
+		assert_eq!(Provider::resource(answered).unwrap().rental_statistics.fault_count, 0);
+		assert_eq!(Provider::resource(missed).unwrap().rental_statistics.fault_count, 1);
+		assert!(provider_events().contains(&crate::Event::ChallengeFailed { index: missed }));
+		assert!(!provider_events().contains(&crate::Event::ChallengeFailed { index: answered }));
+	});
+}
+
+#[test]
+fn audits_skip_offline_and_removed_resources() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		run_to_block(11);
+		assert_eq!(Provider::resource(index).unwrap().status, ResourceStatus::Offline);
+
+		AuditPeriod::set(5);
+		run_to_block(15);
+		assert_eq!(Provider::audit_challenge(index), None);
+
+		assert_ok!(heartbeat(&ALICE, index, 15));
+		run_to_block(20);
+		assert!(Provider::audit_challenge(index).is_some());
+
+		// the challenge of a removed resource is dropped without a fault
+		assert_ok!(Provider::remove_resource(Origin::signed(ALICE), index));
+		assert_eq!(Provider::audit_challenge(index), None);
+		run_to_block(23);
+		assert!(!provider_events().contains(&crate::Event::ChallengeFailed { index }));
+	});
+}
+
+#[test]
+fn answer_challenge_fails() {
+	new_test_ext().execute_with(|| {
+		AuditPeriod::set(5);
+		let index = register(ALICE, b"peer0");
+		assert_noop!(answer(&ALICE, index, H256::zero()), Error::<Test>::ChallengeNotFound);
+
+		run_to_block(5);
+		let nonce = Provider::audit_challenge(index).unwrap().nonce;
+		assert_noop!(
+			Provider::answer_challenge(
+				Origin::signed(ALICE),
+				ChallengeResponsePayload {
+					resource_index: index,
+					nonce,
+					peer_id: b"peer0".to_vec(),
+					public: public(&ALICE)
+				},
+				MultiSignature::from(sr25519::Signature::from_raw([0; 64])),
+			),
+			BadOrigin
+		);
+		assert_noop!(answer(&ALICE, 1, nonce), Error::<Test>::ResourceNotFound);
+		assert_noop!(answer(&BOB, index, nonce), Error::<Test>::NotOwner);
+		assert_noop!(answer(&ALICE, index, H256::zero()), Error::<Test>::InvalidChallengeResponse);
+		// from another node of the provider
+		assert_noop!(answer_from(&ALICE, index, b"peer1", nonce), Error::<Test>::PeerIdMismatch);
+
+		// too late
+		run_to_block(8);
+		assert_noop!(answer(&ALICE, index, nonce), Error::<Test>::ChallengeNotFound);
+	});
+}
+
+#[test]
+fn challenge_response_signature_is_verified() {
+	new_test_ext().execute_with(|| {
+		let key =
+			crypto::Public::generate_pair(Some(format!("0x{}", "05".repeat(32)).into_bytes()));
+		let public: MultiSigner = sr25519::Public::from(key).into();
+		let index = register(public.clone().into_account(), b"peer0");
+		let validate = |response: ChallengeResponsePayload<MultiSigner, H256>,
+		                signature: MultiSignature| {
+			Provider::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::answer_challenge { response, signature },
+			)
+		};
+		let sign = |response: &ChallengeResponsePayload<MultiSigner, H256>| {
+			SignedPayload::<Test>::sign::<crypto::ProviderAuthId>(response).unwrap()
+		};
+		let payload = |nonce| ChallengeResponsePayload {
+			resource_index: index,
+			nonce,
+			peer_id: b"peer0".to_vec(),
+			public: public.clone(),
+		};
+
+		AuditPeriod::set(5);
+		run_to_block(5);
+		let nonce = Provider::audit_challenge(index).unwrap().nonce;
+		assert_ok!(validate(payload(nonce), sign(&payload(nonce))));
+		assert_eq!(
+			validate(payload(nonce), sign(&payload(H256::zero()))),
+			InvalidTransaction::BadProof.into()
+		);
+		assert_eq!(
+			validate(payload(H256::zero()), sign(&payload(H256::zero()))),
+			InvalidTransaction::BadProof.into()
+		);
+
+		run_to_block(8);
+		assert_eq!(
+			validate(payload(nonce), sign(&payload(nonce))),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+#[test]
+fn era_end_records_provider_points() {
+	new_test_ext().execute_with(|| {
+		register(ALICE, b"peer0");
+		register_with(BOB, b"peer1", mock::config(4, 8), 10);
+
+		end_era();
+		assert_eq!(Provider::current_era(), 1);
+		assert_eq!(Provider::points_at(&ALICE, 0), 6);
+		assert_eq!(Provider::points_at(&BOB, 0), 12);
+		assert_eq!(Provider::eras_total_points(0), 18);
+		assert_eq!(Provider::eras_reward_pot(0), Some(1_000));
+		System::assert_last_event(Event::Provider(crate::Event::EraEnded {
+			era: 0,
+			reward_pot: 1_000,
+			total_points: 18,
+		}));
+
+		// points changing in an era are checkpointed at its end
+		register(ALICE, b"peer2");
+		end_era();
+		assert_eq!(Provider::points_at(&ALICE, 0), 6);
+		assert_eq!(Provider::points_at(&ALICE, 1), 12);
+		assert_eq!(Provider::points_history(ALICE).into_inner(), vec![(0, 6), (1, 12)]);
+
+		// eras that can no longer be claimed are forgotten
+		end_era();
+		assert_eq!(Provider::current_era(), 3);
+		assert_eq!(Provider::eras_total_points(0), 0);
+		assert_eq!(Provider::eras_reward_pot(0), None);
+		register(ALICE, b"peer3");
+		assert_eq!(Provider::points_history(ALICE).into_inner(), vec![(1, 12), (3, 18)]);
+		assert_eq!(Provider::points_at(&ALICE, 2), 12);
+	});
+}
+
+#[test]
+fn eras_without_points_have_no_reward() {
+	new_test_ext().execute_with(|| {
+		end_era();
+		assert_eq!(Provider::current_era(), 1);
+		assert_eq!(Provider::eras_reward_pot(0), None);
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(ALICE), ALICE, 0),
+			Error::<Test>::NoRewardForEra
+		);
+	});
+}
+
+#[test]
+fn payout_provider_works() {
+	new_test_ext().execute_with(|| {
+		register(ALICE, b"peer0");
+		register(BOB, b"peer1");
+		end_era();
+
+		// the reward is minted without a reward pot account
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 500);
+		assert_eq!(Provider::claimed_eras(ALICE).into_inner(), vec![0]);
+		System::assert_last_event(Event::Provider(crate::Event::ProviderRewarded {
+			era: 0,
+			provider: ALICE,
+			amount: 500,
+		}));
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0),
+			Error::<Test>::NoRewardForEra
+		);
+
+		// or paid from the reward pot account
+		RewardPotAccount::set(Some(CHARLIE));
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), BOB, 0));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 500);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE - 500);
+	});
+}
+
+#[test]
+fn payout_provider_pays_the_points_at_the_end_of_the_era() {
+	new_test_ext().execute_with(|| {
+		let index = register(ALICE, b"peer0");
+		register(BOB, b"peer1");
+		end_era();
+		assert_ok!(Provider::remove_resource(Origin::signed(ALICE), index));
+		end_era();
+
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 500);
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 1),
+			Error::<Test>::NoRewardForEra
+		);
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), BOB, 1));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 1_000);
+
+		// eras that can no longer be claimed are forgotten from the claimed ones
+		end_era();
+		end_era();
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), BOB, 3));
+		assert_eq!(Provider::claimed_eras(BOB).into_inner(), vec![3]);
+	});
+}
+
+#[test]
+fn payout_provider_fails() {
+	new_test_ext().execute_with(|| {
+		register(ALICE, b"peer0");
+
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0),
+			Error::<Test>::InvalidEra
+		);
+		end_era();
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), BOB, 0),
+			Error::<Test>::NoRewardForEra
+		);
+
+		RewardPotAccount::set(Some(DAVE));
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0),
+			Error::<Test>::RewardPaymentFailed
+		);
+		RewardPotAccount::set(None);
+
+		// era 0 can be claimed until the end of era 2
+		end_era();
+		end_era();
+		assert_noop!(
+			Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 0),
+			Error::<Test>::InvalidEra
+		);
+		assert_ok!(Provider::payout_provider(Origin::signed(CHARLIE), ALICE, 1));
+	});
+}
+
+#[test]
+fn genesis_sets_the_current_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Provider::on_chain_storage_version(), STORAGE_VERSION);
+		assert_eq!(Provider::current_storage_version(), STORAGE_VERSION);
+	});
+}
+
+/// store a resource with 2 cpus and 4 memory in its version 0 encoding at `index`
+fn put_v0_resource(index: u64, who: AccountId, peer_id: &[u8], status: ResourceStatus) {
+	let v0 = (
+		index,
+		who,
+		peer_id.to_vec(),
+		(2u64, 4u64, b"linux".to_vec(), b"Intel Xeon".to_vec()),
+		ResourceRentalStatistics::new(1, 2, 3, 4),
+		ResourceRentalInfo::<BlockNumber>::new(10, 100, 100),
+		status,
+		b"127.0.0.1".to_vec(),
+		Specification::Enhanced,
+	);
+	put_raw(b"Resources", index, v0);
+}
+
+/// store `value` at `key` of the `Twox64Concat` map `item`
+fn put_raw(item: &[u8], key: impl Encode, value: impl Encode) {
+	frame_support::storage::migration::put_storage_value(
+		b"Provider",
+		item,
+		&Twox64Concat::hash(&key.encode()),
+		value,
+	);
+}
+
+#[test]
+fn migration_to_v1_upgrades_the_baseline_resources() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Provider>();
+		put_v0_resource(0, ALICE, b"peer0", ResourceStatus::Inuse);
+		put_v0_resource(1, ALICE, b"peer1", ResourceStatus::Offline);
+		ResourceIndex::<Test>::put(2);
+		System::set_block_number(7);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Provider::on_chain_storage_version(), 1);
+		// no order leases the resource in use
+		let resource = ComputingResource::new(
+			0,
+			ALICE,
+			bounded(b"peer0"),
+			mock::config(2, 4),
+			ResourceRentalStatistics::new(1, 2, 3, 4),
+			ResourceRentalInfo::new(10, 100, 100),
+			ResourceStatus::Unused,
+			bounded(b"127.0.0.1"),
+			Specification::Enhanced,
+			7,
+		);
+		assert_eq!(Provider::resource(0), Some(resource.clone()));
+		assert_eq!(Provider::resource(1).unwrap().status, ResourceStatus::Offline);
+		assert_eq!(Provider::resource_index(), 2);
+		assert_eq!(Provider::peer_id_resource(bounded(b"peer0")), Some(0));
+		assert_eq!(Provider::peer_id_resource(bounded(b"peer1")), Some(1));
+		assert_eq!(Provider::provider(ALICE).unwrap().into_inner(), vec![0, 1]);
+
+		// both resources get a heartbeat deadline, only the online one has points
+		assert_eq!(Provider::resource_heartbeat(0), Some(7));
+		assert_eq!(heartbeat_deadlines(17), vec![0, 1]);
+		assert_eq!(Provider::heartbeat_cursor(), 7);
+		assert_eq!(Provider::provider_points(ALICE).unwrap().resource_points, 6 * 2);
+		assert_eq!(
+			Provider::class_market(resource.class()),
+			Some(ClassMarket { resources: 2, leased: 0, multiplier: FixedU128::one() })
+		);
+		assert!(UnusedResources::<Test>::contains_key(Specification::Enhanced, 0));
+
+		// the resources go offline without heartbeats
+		run_to_block(17);
+		assert_eq!(Provider::resource(0).unwrap().status, ResourceStatus::Offline);
+	});
+}
+
+#[test]
+fn migration_to_v1_removes_resources_it_can_not_register() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Provider>();
+		// an oversized peer id, a peer id taken by a lower index, and alice's fifth resource
+		put_v0_resource(0, CHARLIE, &[b'p'; 40], ResourceStatus::Unused);
+		put_v0_resource(1, CHARLIE, b"peer1", ResourceStatus::Unused);
+		put_v0_resource(2, CHARLIE, b"peer1", ResourceStatus::Unused);
+		for index in 3..8 {
+			put_v0_resource(
+				index,
+				ALICE,
+				format!("peer{}", index).as_bytes(),
+				ResourceStatus::Unused,
+			);
+		}
+		put_raw(b"Providers", ALICE, vec![7u64]);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Provider::resource(0), None);
+		assert_eq!(Provider::resource(2), None);
+		assert_eq!(Provider::resource(7), None);
+		assert_eq!(Provider::peer_id_resource(bounded(b"peer1")), Some(1));
+		assert_eq!(Provider::provider(CHARLIE).unwrap().into_inner(), vec![1]);
+		assert_eq!(Provider::provider(ALICE).unwrap().into_inner(), vec![3, 4, 5, 6]);
+		assert_eq!(Provider::resource_index(), 8);
+	});
+}
+
+#[test]
+fn migration_to_v1_skips_other_storage_versions() {
+	ExtBuilder::default()
+		.resources(vec![resource(0, ALICE, ResourceStatus::Unused)])
+		.build_and_execute(|| {
+			migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Provider::on_chain_storage_version(), 1);
+			assert_eq!(Provider::resource(0), Some(resource(0, ALICE, ResourceStatus::Unused)));
+			assert_eq!(Provider::provider(ALICE).unwrap().into_inner(), vec![0]);
+		});
+}
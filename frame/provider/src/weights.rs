@@ -0,0 +1,1218 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_provider
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2026-10-18, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/release/substrate
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_provider
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/provider/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+// --header=HEADER-APACHE2
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_provider.
+pub trait WeightInfo {
+	fn register_resource() -> Weight;
+	fn update_resource() -> Weight;
+	fn add_resource_duration() -> Weight;
+	fn remove_resource() -> Weight;
+	fn bond_resource() -> Weight;
+	fn unbond_resource() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn payout_provider() -> Weight;
+	fn rent_resource() -> Weight;
+	fn rent_resource_in_asset() -> Weight;
+	fn set_dynamic_pricing() -> Weight;
+	fn set_asset_prices(a: u32, ) -> Weight;
+	fn set_resource_divisible() -> Weight;
+	fn rent_slice() -> Weight;
+	fn rent_by_demand(c: u32, ) -> Weight;
+	fn settle_order() -> Weight;
+	fn renew_order() -> Weight;
+	fn terminate_order() -> Weight;
+	fn reserve_resource() -> Weight;
+	fn cancel_reservation() -> Weight;
+	fn activate_reservation() -> Weight;
+	fn list_auction() -> Weight;
+	fn bid() -> Weight;
+	fn commit_bid() -> Weight;
+	fn reveal_bid() -> Weight;
+	fn close_auction(b: u32, ) -> Weight;
+	fn rate_order() -> Weight;
+	fn refresh_reputation() -> Weight;
+	fn claim_assets() -> Weight;
+	fn validate_unsigned_and_then_heartbeat() -> Weight;
+	fn validate_unsigned_and_then_attest_hardware() -> Weight;
+	fn validate_unsigned_and_then_answer_challenge() -> Weight;
+	fn process_audit() -> Weight;
+	fn process_challenge_deadlines(c: u32, ) -> Weight;
+	fn process_expiries(e: u32, ) -> Weight;
+	fn process_heartbeat_deadlines(h: u32, ) -> Weight;
+	fn process_price_adjustment(c: u32, ) -> Weight;
+	fn process_era() -> Weight;
+	fn set_specification_threshold() -> Weight;
+	fn refresh_specification() -> Weight;
+}
+
+/// Weights for pallet_provider using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider PeerIds (r:1 w:1)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider ResourceIndex (r:1 w:1)
+	// Storage: Provider Providers (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider Resources (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	fn register_resource() -> Weight {
+		(125_120_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceAttestations (r:1 w:0)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	fn update_resource() -> Weight {
+		(146_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	fn add_resource_duration() -> Weight {
+		(41_565_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider Unbonding (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider Providers (r:1 w:1)
+	// Storage: Provider DynamicPrices (r:0 w:1)
+	// Storage: Provider PeerIds (r:0 w:1)
+	// Storage: Provider AuditChallenges (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider ResourceAttestations (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	// Storage: Provider AssetPrices (r:0 w:1)
+	// Storage: Provider ResourceReputations (r:0 w:1)
+	// Storage: Provider ResourceHeartbeats (r:0 w:1)
+	// Storage: Provider FlaggedResources (r:0 w:1)
+	fn remove_resource() -> Weight {
+		(158_913_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	fn bond_resource() -> Weight {
+		(84_719_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider Unbonding (r:1 w:1)
+	fn unbond_resource() -> Weight {
+		(69_182_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Provider Unbonding (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		(46_368_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ClaimedEras (r:1 w:1)
+	// Storage: Provider PointsHistory (r:1 w:0)
+	// Storage: Provider ErasRewardPot (r:1 w:0)
+	// Storage: Provider ErasTotalPoints (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn payout_provider() -> Weight {
+		(94_478_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_resource() -> Weight {
+		(189_067_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(25 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider AssetPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider OrderAssets (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_resource_in_asset() -> Weight {
+		(261_630_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(29 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:0 w:1)
+	fn set_dynamic_pricing() -> Weight {
+		(40_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider AssetPrices (r:0 w:1)
+	fn set_asset_prices(a: u32, ) -> Weight {
+		(27_678_000 as Weight)
+			// Standard Error: 79_000
+			.saturating_add((454_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	fn set_resource_divisible() -> Weight {
+		(65_254_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_slice() -> Weight {
+		(388_709_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(25 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider UnusedResources (r:4 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	fn rent_by_demand(c: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 499_000
+			.saturating_add((64_916_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(24 as Weight))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn settle_order() -> Weight {
+		(192_466_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider OrderAssets (r:1 w:0)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	fn renew_order() -> Weight {
+		(209_976_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn terminate_order() -> Weight {
+		(180_403_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider RentalOrders (r:16 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider ReservationIndex (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider Reservations (r:0 w:1)
+	fn reserve_resource() -> Weight {
+		(263_806_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(26 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Provider Reservations (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	fn cancel_reservation() -> Weight {
+		(96_136_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Provider Reservations (r:1 w:1)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RentalOrders (r:16 w:17)
+	// Storage: Provider OrderAssets (r:16 w:16)
+	// Storage: System Account (r:17 w:17)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider RenterOrders (r:17 w:17)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	// Storage: Provider OrderRatings (r:0 w:16)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn activate_reservation() -> Weight {
+		(2_159_674_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(96 as Weight))
+			.saturating_add(T::DbWeight::get().writes(97 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider AuctionIndex (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider Auctions (r:0 w:1)
+	fn list_auction() -> Weight {
+		(101_542_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:1 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn bid() -> Weight {
+		(113_908_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:1 w:1)
+	fn commit_bid() -> Weight {
+		(90_408_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn reveal_bid() -> Weight {
+		(107_431_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:2 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider ResourceAuctions (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn close_auction(b: u32, ) -> Weight {
+		(316_893_000 as Weight)
+			// Standard Error: 231_000
+			.saturating_add((49_692_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(24 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+	}
+	// Storage: Provider RentalOrders (r:1 w:0)
+	// Storage: Provider OrderRatings (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	fn rate_order() -> Weight {
+		(62_453_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	fn refresh_reputation() -> Weight {
+		(44_661_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider UnclaimedAssets (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn claim_assets() -> Weight {
+		(94_243_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:0 w:1)
+	fn validate_unsigned_and_then_heartbeat() -> Weight {
+		(225_566_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceAttestations (r:1 w:1)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Provider UnusedResources (r:0 w:2)
+	fn validate_unsigned_and_then_attest_hardware() -> Weight {
+		(288_768_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider AuditChallenges (r:1 w:1)
+	fn validate_unsigned_and_then_answer_challenge() -> Weight {
+		(167_687_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider ResourceIndex (r:1 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Provider Resources (r:15 w:0)
+	// Storage: Provider AuditChallenges (r:15 w:15)
+	// Storage: Provider ChallengeDeadlines (r:0 w:1)
+	fn process_audit() -> Weight {
+		(1_090_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(32 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: Provider ChallengeDeadlines (r:1 w:1)
+	// Storage: Provider AuditChallenges (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	fn process_challenge_deadlines(c: u32, ) -> Weight {
+		(11_018_000 as Weight)
+			// Standard Error: 335_000
+			.saturating_add((41_888_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Provider OrderExpiries (r:1 w:0)
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn process_expiries(e: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 2_086_000
+			.saturating_add((179_956_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(e as Weight)))
+	}
+	// Storage: Provider HeartbeatCursor (r:1 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:1 w:0)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider RentalOrders (r:16 w:0)
+	// Storage: Provider OrderAssets (r:16 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: System Account (r:17 w:17)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn process_heartbeat_deadlines(h: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 11_498_000
+			.saturating_add((1_157_276_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((56 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((24 as Weight).saturating_mul(h as Weight)))
+	}
+	// Storage: Provider PriceAdjustmentCursor (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:0)
+	fn process_price_adjustment(c: u32, ) -> Weight {
+		(9_938_000 as Weight)
+			// Standard Error: 244_000
+			.saturating_add((16_809_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Provider CurrentEra (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:0)
+	// Storage: Provider TotalDurationPoints (r:1 w:0)
+	// Storage: Provider ErasRewardPot (r:0 w:2)
+	// Storage: Provider ErasTotalPoints (r:0 w:2)
+	fn process_era() -> Weight {
+		(41_939_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Provider SpecificationThresholds (r:0 w:1)
+	fn set_specification_threshold() -> Weight {
+		(22_255_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceAttestations (r:1 w:0)
+	// Storage: Provider ResourceScores (r:1 w:0)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Provider UnusedResources (r:0 w:2)
+	fn refresh_specification() -> Weight {
+		(152_152_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider PeerIds (r:1 w:1)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider ResourceIndex (r:1 w:1)
+	// Storage: Provider Providers (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider Resources (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	fn register_resource() -> Weight {
+		(125_120_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceAttestations (r:1 w:0)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	fn update_resource() -> Weight {
+		(146_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	fn add_resource_duration() -> Weight {
+		(41_565_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider Unbonding (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider Providers (r:1 w:1)
+	// Storage: Provider DynamicPrices (r:0 w:1)
+	// Storage: Provider PeerIds (r:0 w:1)
+	// Storage: Provider AuditChallenges (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider ResourceAttestations (r:0 w:1)
+	// Storage: Provider ResourceScores (r:0 w:1)
+	// Storage: Provider AssetPrices (r:0 w:1)
+	// Storage: Provider ResourceReputations (r:0 w:1)
+	// Storage: Provider ResourceHeartbeats (r:0 w:1)
+	// Storage: Provider FlaggedResources (r:0 w:1)
+	fn remove_resource() -> Weight {
+		(158_913_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(19 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	fn bond_resource() -> Weight {
+		(84_719_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: Provider Unbonding (r:1 w:1)
+	fn unbond_resource() -> Weight {
+		(69_182_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Provider Unbonding (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		(46_368_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ClaimedEras (r:1 w:1)
+	// Storage: Provider PointsHistory (r:1 w:0)
+	// Storage: Provider ErasRewardPot (r:1 w:0)
+	// Storage: Provider ErasTotalPoints (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn payout_provider() -> Weight {
+		(94_478_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_resource() -> Weight {
+		(189_067_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(25 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider AssetPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider OrderAssets (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_resource_in_asset() -> Weight {
+		(261_630_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(29 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:0 w:1)
+	fn set_dynamic_pricing() -> Weight {
+		(40_902_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider AssetPrices (r:0 w:1)
+	fn set_asset_prices(a: u32, ) -> Weight {
+		(27_678_000 as Weight)
+			// Standard Error: 79_000
+			.saturating_add((454_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	fn set_resource_divisible() -> Weight {
+		(65_254_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn rent_slice() -> Weight {
+		(388_709_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(25 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider UnusedResources (r:4 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:0)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:15 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	fn rent_by_demand(c: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 499_000
+			.saturating_add((64_916_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(24 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn settle_order() -> Weight {
+		(192_466_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider OrderAssets (r:1 w:0)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	fn renew_order() -> Weight {
+		(209_976_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn terminate_order() -> Weight {
+		(180_403_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	// Storage: Provider ResourceAuctions (r:1 w:0)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider RentalOrders (r:16 w:0)
+	// Storage: Provider DynamicPrices (r:1 w:0)
+	// Storage: Provider ReservationIndex (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider Reservations (r:0 w:1)
+	fn reserve_resource() -> Weight {
+		(263_806_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(26 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Provider Reservations (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	fn cancel_reservation() -> Weight {
+		(96_136_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Provider Reservations (r:1 w:1)
+	// Storage: Provider ResourceReservations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RentalOrders (r:16 w:17)
+	// Storage: Provider OrderAssets (r:16 w:16)
+	// Storage: System Account (r:17 w:17)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider RenterOrders (r:17 w:17)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	// Storage: Provider OrderRatings (r:0 w:16)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn activate_reservation() -> Weight {
+		(2_159_674_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(96 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(97 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider ResourceAuctions (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider ResourceReservations (r:1 w:0)
+	// Storage: Provider AuctionIndex (r:1 w:1)
+	// Storage: Scheduler Lookup (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	// Storage: Provider Auctions (r:0 w:1)
+	fn list_auction() -> Weight {
+		(101_542_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:1 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn bid() -> Weight {
+		(113_908_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:1 w:1)
+	fn commit_bid() -> Weight {
+		(90_408_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn reveal_bid() -> Weight {
+		(107_431_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Provider Auctions (r:1 w:1)
+	// Storage: Provider AuctionBids (r:2 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider OrderIndex (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderExpiries (r:16 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider RentalOrders (r:0 w:1)
+	// Storage: Provider ResourceAuctions (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn close_auction(b: u32, ) -> Weight {
+		(316_893_000 as Weight)
+			// Standard Error: 231_000
+			.saturating_add((49_692_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(24 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+	}
+	// Storage: Provider RentalOrders (r:1 w:0)
+	// Storage: Provider OrderRatings (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	fn rate_order() -> Weight {
+		(62_453_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	fn refresh_reputation() -> Weight {
+		(44_661_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider UnclaimedAssets (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn claim_assets() -> Weight {
+		(94_243_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:0 w:1)
+	fn validate_unsigned_and_then_heartbeat() -> Weight {
+		(225_566_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceAttestations (r:1 w:1)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Provider UnusedResources (r:0 w:2)
+	fn validate_unsigned_and_then_attest_hardware() -> Weight {
+		(288_768_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:0)
+	// Storage: Provider AuditChallenges (r:1 w:1)
+	fn validate_unsigned_and_then_answer_challenge() -> Weight {
+		(167_687_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider ResourceIndex (r:1 w:0)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Provider Resources (r:15 w:0)
+	// Storage: Provider AuditChallenges (r:15 w:15)
+	// Storage: Provider ChallengeDeadlines (r:0 w:1)
+	fn process_audit() -> Weight {
+		(1_090_874_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	// Storage: Provider ChallengeDeadlines (r:1 w:1)
+	// Storage: Provider AuditChallenges (r:1 w:1)
+	// Storage: Provider Resources (r:1 w:1)
+	fn process_challenge_deadlines(c: u32, ) -> Weight {
+		(11_018_000 as Weight)
+			// Standard Error: 335_000
+			.saturating_add((41_888_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Provider OrderExpiries (r:1 w:0)
+	// Storage: Provider RentalOrders (r:1 w:1)
+	// Storage: Provider OrderAssets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:1)
+	// Storage: Provider ResourceReputations (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:1)
+	// Storage: Provider RenterOrders (r:1 w:1)
+	// Storage: Provider OrderRatings (r:0 w:1)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn process_expiries(e: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 2_086_000
+			.saturating_add((179_956_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((12 as Weight).saturating_mul(e as Weight)))
+	}
+	// Storage: Provider HeartbeatCursor (r:1 w:1)
+	// Storage: Provider HeartbeatDeadlines (r:1 w:0)
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceHeartbeats (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider RentalOrders (r:16 w:0)
+	// Storage: Provider OrderAssets (r:16 w:0)
+	// Storage: Provider ResourceBonds (r:1 w:1)
+	// Storage: System Account (r:17 w:17)
+	// Storage: Provider UnusedResources (r:0 w:1)
+	fn process_heartbeat_deadlines(h: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 11_498_000
+			.saturating_add((1_157_276_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((56 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((24 as Weight).saturating_mul(h as Weight)))
+	}
+	// Storage: Provider PriceAdjustmentCursor (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:1 w:0)
+	fn process_price_adjustment(c: u32, ) -> Weight {
+		(9_938_000 as Weight)
+			// Standard Error: 244_000
+			.saturating_add((16_809_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: Provider CurrentEra (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:0)
+	// Storage: Provider TotalDurationPoints (r:1 w:0)
+	// Storage: Provider ErasRewardPot (r:0 w:2)
+	// Storage: Provider ErasTotalPoints (r:0 w:2)
+	fn process_era() -> Weight {
+		(41_939_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Provider SpecificationThresholds (r:0 w:1)
+	fn set_specification_threshold() -> Weight {
+		(22_255_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Provider Resources (r:1 w:1)
+	// Storage: Provider ResourceLeases (r:1 w:0)
+	// Storage: Provider ResourceAttestations (r:1 w:0)
+	// Storage: Provider ResourceScores (r:1 w:0)
+	// Storage: Provider SpecificationThresholds (r:3 w:0)
+	// Storage: Provider FlaggedResources (r:1 w:0)
+	// Storage: Provider Points (r:1 w:1)
+	// Storage: Provider CurrentEra (r:1 w:0)
+	// Storage: Provider PointsHistory (r:1 w:1)
+	// Storage: Provider TotalResourcePoints (r:1 w:1)
+	// Storage: Provider TotalDurationPoints (r:1 w:1)
+	// Storage: Provider ClassMarkets (r:2 w:2)
+	// Storage: Provider UnusedResources (r:0 w:2)
+	fn refresh_specification() -> Weight {
+		(152_152_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+}
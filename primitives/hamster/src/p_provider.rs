@@ -1,26 +1,76 @@
-use codec::{Decode, Encode};
-use frame_support::{sp_runtime::traits::AtLeast32BitUnsigned, Parameter};
+use crate::EraIndex;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	sp_runtime::{
+		traits::{AtLeast32BitUnsigned, One, Saturating},
+		FixedPointNumber, FixedU128, Perbill,
+	},
+	traits::Get,
+	BoundedVec, CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_debug_derive::RuntimeDebug;
 use sp_std::vec::Vec;
 
+parameter_types! {
+	/// maximum length of the byte strings describing a resource, the runtime api and rpc use it
+	/// so runtimes are expected to configure the provider pallet with it
+	pub const ResourceStringLimit: u32 = 128;
+	/// maximum number of dimensions, besides cpu and memory, of a resource configuration or
+	/// demand
+	pub const MaxResourceDimensions: u32 = 16;
+}
+
+/// key of the persistent offchain storage holding the encoded `HardwareScores` last measured by
+/// the node, attested on chain by the provider offchain worker
+pub const HWBENCH_STORAGE_KEY: &[u8] = b"provider::hwbench";
+
+/// key of the persistent offchain storage holding the encoded base58 peer id of the node, only
+/// the resources registered with it are attested by the provider offchain worker
+pub const PEER_ID_STORAGE_KEY: &[u8] = b"provider::peer_id";
+
+/// best rating a renter can give to a resource, the worst being 1
+pub const MAX_RATING: u8 = 5;
+
 /// ComputingResources
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ComputingResource<BlockNumber, AccountId>
+#[cfg_attr(
+	feature = "std",
+	serde(bound(
+		serialize = "BlockNumber: Serialize, AccountId: Serialize",
+		deserialize = "BlockNumber: Deserialize<'de>, AccountId: Deserialize<'de>"
+	))
+)]
+#[scale_info(skip_type_params(StringLimit))]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen, AccountId: MaxEncodedLen))]
+pub struct ComputingResource<BlockNumber, AccountId, StringLimit>
 where
 	BlockNumber: Parameter + AtLeast32BitUnsigned,
+	AccountId: Parameter,
+	StringLimit: Get<u32>,
 {
 	/// computing power resource index
 	pub index: u64,
 	/// provider account
 	pub account_id: AccountId,
 	/// computing resource link id
-	pub peer_id: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub peer_id: BoundedVec<u8, StringLimit>,
 	/// resource configuration
-	pub config: ResourceConfig,
+	pub config: ResourceConfig<StringLimit>,
 	/// resource rental statistics
 	pub rental_statistics: ResourceRentalStatistics,
 	/// resource rental information
@@ -28,12 +78,21 @@ where
 	/// resource lease status
 	pub status: ResourceStatus,
 	/// resource public ip
-	pub public_ip: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub public_ip: BoundedVec<u8, StringLimit>,
 	/// resource specification
 	pub specification: Specification,
+	/// block at which the resource was registered
+	pub registered_at: BlockNumber,
+	/// whether slices of the cpus and memory of the resource can be leased
+	pub divisible: bool,
+	/// cpus and memory leased by the active orders of the resource
+	pub allocated: ResourceSlice,
 }
 
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Specification {
 	General,
@@ -41,20 +100,105 @@ pub enum Specification {
 	HighRanking,
 }
 
-impl<BlockNumber, AccountId> ComputingResource<BlockNumber, AccountId>
+/// hardware benchmark scores of a resource, as measured by `sc_sysinfo::gather_hwbench`
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HardwareScores {
+	/// cpu speed, in MB/s hashed with BLAKE2b-256
+	pub cpu_hashrate_score: u64,
+	/// memory bandwidth in MB/s, measured with `memcpy`
+	pub memory_memcpy_score: u64,
+	/// sequential disk write speed in MB/s, if the disk was benchmarked
+	pub disk_sequential_write_score: Option<u64>,
+	/// random disk write speed in MB/s, if the disk was benchmarked
+	pub disk_random_write_score: Option<u64>,
+}
+
+impl HardwareScores {
+	/// whether no score exceeds the one of `other`, unmeasured disks having a speed of zero
+	pub fn is_within(&self, other: &HardwareScores) -> bool {
+		self.cpu_hashrate_score <= other.cpu_hashrate_score &&
+			self.memory_memcpy_score <= other.memory_memcpy_score &&
+			self.disk_sequential_write_score.unwrap_or_default() <=
+				other.disk_sequential_write_score.unwrap_or_default() &&
+			self.disk_random_write_score.unwrap_or_default() <=
+				other.disk_random_write_score.unwrap_or_default()
+	}
+}
+
+/// hardware benchmark scores of a resource, signed by its node
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HardwareAttestation<BlockNumber> {
+	pub scores: HardwareScores,
+	/// block the attestation has been included at
+	pub block_number: BlockNumber,
+	/// unix time of the block the attestation has been included at, in milliseconds
+	pub timestamp: u64,
+}
+
+/// audit challenge of a resource, answered by signing its nonce with the provider key
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuditChallenge<BlockNumber, Hash> {
+	/// random value to sign
+	pub nonce: Hash,
+	/// block at which the unanswered challenge counts as a fault
+	pub deadline: BlockNumber,
+}
+
+/// minimum declared capacity and measured scores of the resources of a specification
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SpecificationThreshold {
+	/// minimum number of cpus
+	pub cpu: u64,
+	/// minimum memory
+	pub memory: u64,
+	/// minimum cpu speed in MB/s
+	pub cpu_hashrate_score: u64,
+	/// minimum memory bandwidth in MB/s
+	pub memory_memcpy_score: u64,
+	/// minimum sequential disk write speed in MB/s, unmeasured disks have a speed of zero
+	pub disk_sequential_write_score: u64,
+	/// minimum random disk write speed in MB/s, unmeasured disks have a speed of zero
+	pub disk_random_write_score: u64,
+}
+
+impl SpecificationThreshold {
+	/// whether a resource with `config` and `scores` reaches the threshold
+	pub fn is_met_by<StringLimit: Get<u32>>(
+		&self,
+		config: &ResourceConfig<StringLimit>,
+		scores: &HardwareScores,
+	) -> bool {
+		config.cpu >= self.cpu &&
+			config.memory >= self.memory &&
+			scores.cpu_hashrate_score >= self.cpu_hashrate_score &&
+			scores.memory_memcpy_score >= self.memory_memcpy_score &&
+			scores.disk_sequential_write_score.unwrap_or_default() >=
+				self.disk_sequential_write_score &&
+			scores.disk_random_write_score.unwrap_or_default() >= self.disk_random_write_score
+	}
+}
+
+impl<BlockNumber, AccountId, StringLimit> ComputingResource<BlockNumber, AccountId, StringLimit>
 where
 	BlockNumber: Parameter + AtLeast32BitUnsigned,
+	AccountId: Parameter,
+	StringLimit: Get<u32>,
 {
 	pub fn new(
 		index: u64,
 		account_id: AccountId,
-		peer_id: Vec<u8>,
-		config: ResourceConfig,
+		peer_id: BoundedVec<u8, StringLimit>,
+		config: ResourceConfig<StringLimit>,
 		rental_statistics: ResourceRentalStatistics,
 		rental_info: ResourceRentalInfo<BlockNumber>,
 		status: ResourceStatus,
-		public_ip: Vec<u8>,
+		public_ip: BoundedVec<u8, StringLimit>,
 		specification: Specification,
+		registered_at: BlockNumber,
 	) -> Self {
 		ComputingResource {
 			index,
@@ -66,9 +210,37 @@ where
 			status,
 			public_ip,
 			specification,
+			registered_at,
+			divisible: false,
+			allocated: Default::default(),
 		}
 	}
 
+	/// cpus and memory of the resource
+	pub fn capacity(&self) -> ResourceSlice {
+		ResourceSlice::new(self.config.cpu, self.config.memory)
+	}
+
+	/// cpus and memory not leased by any order
+	pub fn available(&self) -> ResourceSlice {
+		self.capacity().saturating_sub(self.allocated)
+	}
+
+	/// whether all the cpus and memory of the resource are leased
+	pub fn is_fully_allocated(&self) -> bool {
+		self.available().is_empty()
+	}
+
+	/// share of the resource allocated to leases
+	pub fn allocated_share(&self) -> Perbill {
+		self.allocated.share_of(&self.capacity())
+	}
+
+	/// marketplace class of the resource
+	pub fn class(&self) -> ResourceClass {
+		ResourceClass::new(self.specification.clone(), self.config.cpu, self.config.memory)
+	}
+
 	/// update unit price
 	pub fn update_resource_price(&mut self, rent_unit_price: u128) {
 		self.rental_info.set_rent_unit_price(rent_unit_price);
@@ -76,8 +248,7 @@ where
 
 	/// increase rental time
 	pub fn add_resource_duration(&mut self, duration: BlockNumber) {
-		self.rental_info.rent_duration += duration.clone();
-		self.rental_info.end_of_rent += duration;
+		self.rental_info.add_rent_duration(duration);
 	}
 
 	/// update status
@@ -87,7 +258,7 @@ where
 }
 
 /// Provider points
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ProviderPoints {
 	pub total_points: u128,
@@ -119,7 +290,7 @@ impl ProviderPoints {
 	}
 }
 
-#[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, Copy, Clone, TypeInfo)]
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, Copy, Clone, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ResourceStatus {
 	/// using
@@ -132,24 +303,161 @@ pub enum ResourceStatus {
 	Offline,
 }
 
+/// kind of disk, ordered from the slowest to the fastest
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Copy,
+	Clone,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DiskKind {
+	Hdd,
+	Ssd,
+	Nvme,
+}
+
+/// dimension of a resource besides its cpu and memory
+///
+/// a configuration declares what the resource has, a demand what it must at least have
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+#[scale_info(skip_type_params(StringLimit))]
+#[codec(mel_bound())]
+pub enum ResourceDimension<StringLimit: Get<u32>> {
+	/// disk storage, with its capacity in the unit of memory
+	Storage { kind: DiskKind, capacity: u64 },
+	/// network bandwidth in Mbps
+	Bandwidth(u64),
+	/// accelerators of a model, with the memory of each one in the unit of memory
+	Accelerator {
+		#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+		model: BoundedVec<u8, StringLimit>,
+		count: u32,
+		memory: u64,
+	},
+}
+
+impl<StringLimit: Get<u32>> ResourceDimension<StringLimit> {
+	/// whether the dimension describes anything, an empty dimension is never declared
+	pub fn is_valid(&self) -> bool {
+		match self {
+			ResourceDimension::Storage { capacity, .. } => *capacity > 0,
+			ResourceDimension::Bandwidth(bandwidth) => *bandwidth > 0,
+			ResourceDimension::Accelerator { model, count, .. } => !model.is_empty() && *count > 0,
+		}
+	}
+}
+
+/// dimensions of a resource configuration or demand
+pub type ResourceDimensions<StringLimit> =
+	BoundedVec<ResourceDimension<StringLimit>, MaxResourceDimensions>;
+
 /// resource configuration
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ResourceConfig {
+#[cfg_attr(feature = "std", serde(bound = ""))]
+#[scale_info(skip_type_params(StringLimit))]
+#[codec(mel_bound())]
+pub struct ResourceConfig<StringLimit: Get<u32>> {
 	pub cpu: u64,
 	pub memory: u64,
-	pub system: Vec<u8>,
-	pub cpu_model: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub system: BoundedVec<u8, StringLimit>,
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub cpu_model: BoundedVec<u8, StringLimit>,
+	/// storage, bandwidth and accelerators of the resource
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub dimensions: ResourceDimensions<StringLimit>,
 }
 
-impl ResourceConfig {
-	pub fn new(cpu: u64, memory: u64, system: Vec<u8>, cpu_model: Vec<u8>) -> Self {
-		Self { cpu, memory, system, cpu_model }
+impl<StringLimit: Get<u32>> ResourceConfig<StringLimit> {
+	pub fn new(
+		cpu: u64,
+		memory: u64,
+		system: BoundedVec<u8, StringLimit>,
+		cpu_model: BoundedVec<u8, StringLimit>,
+		dimensions: ResourceDimensions<StringLimit>,
+	) -> Self {
+		Self { cpu, memory, system, cpu_model, dimensions }
+	}
+
+	/// total capacity of the disks at least as fast as `kind`
+	pub fn storage(&self, kind: DiskKind) -> u64 {
+		self.dimensions
+			.iter()
+			.filter_map(|dimension| match dimension {
+				ResourceDimension::Storage { kind: disk, capacity } if *disk >= kind =>
+					Some(*capacity),
+				_ => None,
+			})
+			.fold(0, u64::saturating_add)
+	}
+
+	/// total network bandwidth
+	pub fn bandwidth(&self) -> u64 {
+		self.dimensions
+			.iter()
+			.filter_map(|dimension| match dimension {
+				ResourceDimension::Bandwidth(bandwidth) => Some(*bandwidth),
+				_ => None,
+			})
+			.fold(0, u64::saturating_add)
+	}
+
+	/// number of accelerators, of `model` with at least `memory` each if given
+	pub fn accelerators(&self, model: Option<(&[u8], u64)>) -> u32 {
+		self.dimensions
+			.iter()
+			.filter_map(|dimension| match dimension {
+				ResourceDimension::Accelerator { model: m, count, memory: mem }
+					if model
+						.is_none_or(|(model, memory)| m.as_slice() == model && *mem >= memory) =>
+					Some(*count),
+				_ => None,
+			})
+			.fold(0, u32::saturating_add)
+	}
+
+	/// whether the configuration has at least what `required` describes
+	pub fn provides(&self, required: &ResourceDimension<StringLimit>) -> bool {
+		match required {
+			ResourceDimension::Storage { kind, capacity } => self.storage(*kind) >= *capacity,
+			ResourceDimension::Bandwidth(bandwidth) => self.bandwidth() >= *bandwidth,
+			ResourceDimension::Accelerator { model, count, memory } =>
+				self.accelerators(Some((model, *memory))) >= *count,
+		}
 	}
 }
 
 /// resource statistics
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ResourceRentalStatistics {
 	/// number of leases
@@ -193,8 +501,144 @@ impl ResourceRentalStatistics {
 	}
 }
 
+/// reputation of a resource, from its rental statistics and the ratings of its renters, the
+/// counters decaying at every era
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Reputation {
+	/// decayed number of blocks the resource has been leased
+	pub leased: u64,
+	/// decayed number of blocks the resource has been faulty, including a penalty per fault
+	pub faulty: u64,
+	/// decayed sum of the ratings of the renters, in thousandths of the best rating
+	pub ratings: u64,
+	/// decayed number of ratings, in thousandths
+	pub rating_count: u64,
+	/// rental statistics of the resource already counted
+	pub statistics: ResourceRentalStatistics,
+	/// era the counters last decayed at
+	pub era: EraIndex,
+	/// reliability and ratings of the resource combined
+	pub score: Perbill,
+}
+
+impl Reputation {
+	/// reputation of a resource without any history at `era`
+	pub fn new(era: EraIndex) -> Self {
+		let mut reputation = Reputation { era, ..Default::default() };
+		reputation.update_score();
+		reputation
+	}
+
+	/// decay the counters by keeping `kept` of them at every era until `era`
+	pub fn decay(&mut self, kept: Perbill, era: EraIndex) {
+		let eras = era.saturating_sub(self.era);
+		if eras == 0 {
+			return
+		}
+		let kept = kept.saturating_pow(eras as usize);
+		self.leased = kept * self.leased;
+		self.faulty = kept * self.faulty;
+		self.ratings = kept * self.ratings;
+		self.rating_count = kept * self.rating_count;
+		self.era = era;
+	}
+
+	/// count the changes of the rental statistics since they were last counted, every fault
+	/// counting as `fault_penalty` more faulty blocks
+	pub fn count(&mut self, statistics: &ResourceRentalStatistics, fault_penalty: u64) {
+		let counted = &self.statistics;
+		let leased = statistics.rental_duration.saturating_sub(counted.rental_duration);
+		let faults = statistics.fault_count.saturating_sub(counted.fault_count);
+		let faulty = statistics.fault_duration.saturating_sub(counted.fault_duration);
+
+		self.leased = self.leased.saturating_add(leased.into());
+		self.faulty = self
+			.faulty
+			.saturating_add(faulty.into())
+			.saturating_add(fault_penalty.saturating_mul(faults.into()));
+		self.statistics = statistics.clone();
+	}
+
+	/// add a rating from 1 to `MAX_RATING`
+	pub fn rate(&mut self, rating: u8) {
+		let rating = u64::from(rating.min(MAX_RATING)) * 1_000 / u64::from(MAX_RATING);
+		self.ratings = self.ratings.saturating_add(rating);
+		self.rating_count = self.rating_count.saturating_add(1_000);
+	}
+
+	/// derive the score from the share of leased blocks among leased and faulty ones and the
+	/// average rating, a resource without any history being neutral
+	pub fn update_score(&mut self) {
+		let reliability = (self.leased.saturating_add(self.faulty) > 0)
+			.then(|| Perbill::from_rational(self.leased, self.leased.saturating_add(self.faulty)));
+		let rating = (self.rating_count > 0)
+			.then(|| Perbill::from_rational(self.ratings, self.rating_count));
+		self.score = match (reliability, rating) {
+			(Some(reliability), Some(rating)) =>
+				Perbill::from_parts((reliability.deconstruct() + rating.deconstruct()) / 2),
+			(Some(score), None) | (None, Some(score)) => score,
+			(None, None) => Perbill::from_percent(50),
+		};
+	}
+}
+
+/// cpus and memory of a resource leased by an order
+#[derive(
+	PartialEq, Eq, Copy, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ResourceSlice {
+	/// number of cpus
+	pub cpu: u64,
+	/// memory
+	pub memory: u64,
+}
+
+impl ResourceSlice {
+	pub fn new(cpu: u64, memory: u64) -> Self {
+		ResourceSlice { cpu, memory }
+	}
+
+	/// whether the slice holds neither cpu nor memory
+	pub fn is_empty(&self) -> bool {
+		self.cpu == 0 && self.memory == 0
+	}
+
+	/// whether the slice fits in `capacity`
+	pub fn fits_in(&self, capacity: &ResourceSlice) -> bool {
+		self.cpu <= capacity.cpu && self.memory <= capacity.memory
+	}
+
+	pub fn saturating_add(self, other: ResourceSlice) -> Self {
+		ResourceSlice::new(
+			self.cpu.saturating_add(other.cpu),
+			self.memory.saturating_add(other.memory),
+		)
+	}
+
+	pub fn saturating_sub(self, other: ResourceSlice) -> Self {
+		ResourceSlice::new(
+			self.cpu.saturating_sub(other.cpu),
+			self.memory.saturating_sub(other.memory),
+		)
+	}
+
+	/// share of `capacity` taken by the slice, the largest of its cpu and memory shares
+	pub fn share_of(&self, capacity: &ResourceSlice) -> Perbill {
+		let share = |part: u64, total: u64| {
+			if part == 0 {
+				Perbill::zero()
+			} else {
+				Perbill::from_rational(part, total)
+			}
+		};
+		share(self.cpu, capacity.cpu).max(share(self.memory, capacity.memory))
+	}
+}
+
 /// resource rental information
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ResourceRentalInfo<BlockNumber> {
 	/// rental unit price
@@ -205,7 +649,10 @@ pub struct ResourceRentalInfo<BlockNumber> {
 	pub end_of_rent: BlockNumber,
 }
 
-impl<BlockNumber> ResourceRentalInfo<BlockNumber> {
+impl<BlockNumber> ResourceRentalInfo<BlockNumber>
+where
+	BlockNumber: Parameter + AtLeast32BitUnsigned,
+{
 	pub fn new(
 		rent_unit_price: u128,
 		rent_duration: BlockNumber,
@@ -222,9 +669,363 @@ impl<BlockNumber> ResourceRentalInfo<BlockNumber> {
 		self.rent_unit_price = rent_unit_price;
 		self
 	}
+
+	/// increase rental time
+	pub fn add_rent_duration(&mut self, duration: BlockNumber) {
+		self.rent_duration += duration.clone();
+		self.end_of_rent += duration;
+	}
+}
+
+/// resource rental order
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RentalOrder<BlockNumber, AccountId> {
+	/// order index
+	pub index: u64,
+	/// rented resource index
+	pub resource_index: u64,
+	/// renter account
+	pub renter: AccountId,
+	/// provider account
+	pub provider: AccountId,
+	/// cpus and memory leased, all the ones of the resource unless it is divisible
+	pub slice: ResourceSlice,
+	/// lease information, the unit price is fixed when the order is created
+	pub rental_info: ResourceRentalInfo<BlockNumber>,
+	/// start rental block
+	pub start_of_rent: BlockNumber,
+	/// amount reserved from the renter
+	pub deposit: u128,
+}
+
+impl<BlockNumber, AccountId> RentalOrder<BlockNumber, AccountId> {
+	pub fn new(
+		index: u64,
+		resource_index: u64,
+		renter: AccountId,
+		provider: AccountId,
+		slice: ResourceSlice,
+		rental_info: ResourceRentalInfo<BlockNumber>,
+		start_of_rent: BlockNumber,
+		deposit: u128,
+	) -> Self {
+		RentalOrder {
+			index,
+			resource_index,
+			renter,
+			provider,
+			slice,
+			rental_info,
+			start_of_rent,
+			deposit,
+		}
+	}
+}
+
+/// booking of a whole resource for the blocks `[start, end)`, leased to the renter at `start`
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Reservation<BlockNumber, AccountId> {
+	/// reservation index
+	pub index: u64,
+	/// reserved resource index
+	pub resource_index: u64,
+	/// renter account
+	pub renter: AccountId,
+	/// provider account
+	pub provider: AccountId,
+	/// unit price of the lease, fixed when the resource is reserved
+	pub rent_unit_price: u128,
+	/// first reserved block
+	pub start: BlockNumber,
+	/// first block after the reservation
+	pub end: BlockNumber,
+	/// amount reserved from the renter until the lease starts
+	pub deposit: u128,
+}
+
+impl<BlockNumber, AccountId> Reservation<BlockNumber, AccountId> {
+	pub fn new(
+		index: u64,
+		resource_index: u64,
+		renter: AccountId,
+		provider: AccountId,
+		rent_unit_price: u128,
+		start: BlockNumber,
+		end: BlockNumber,
+		deposit: u128,
+	) -> Self {
+		Reservation {
+			index,
+			resource_index,
+			renter,
+			provider,
+			rent_unit_price,
+			start,
+			end,
+			deposit,
+		}
+	}
+}
+
+/// marketplace class of resources, their specification with their cpus and memory rounded down
+/// to powers of two
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ResourceClass {
+	pub specification: Specification,
+	pub cpu: u64,
+	pub memory: u64,
+}
+
+impl ResourceClass {
+	pub fn new(specification: Specification, cpu: u64, memory: u64) -> Self {
+		ResourceClass {
+			specification,
+			cpu: floor_power_of_two(cpu),
+			memory: floor_power_of_two(memory),
+		}
+	}
+}
+
+/// largest power of two not above `n`, zero for zero
+fn floor_power_of_two(n: u64) -> u64 {
+	if n == 0 {
+		return 0
+	}
+	1 << (63 - n.leading_zeros())
+}
+
+/// utilization of the resources of a class and multiplier of their dynamic unit prices
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClassMarket {
+	/// number of registered resources
+	pub resources: u32,
+	/// sum of the shares of the resources allocated to leases, in billionths of a resource
+	pub leased: u64,
+	/// multiplier of the unit prices of the dynamically priced resources
+	pub multiplier: FixedU128,
+}
+
+impl Default for ClassMarket {
+	fn default() -> Self {
+		ClassMarket { resources: 0, leased: 0, multiplier: FixedU128::one() }
+	}
+}
+
+impl ClassMarket {
+	/// allocated share of the resources
+	pub fn utilization(&self) -> FixedU128 {
+		let capacity =
+			u64::from(self.resources).saturating_mul(Perbill::one().deconstruct().into());
+		FixedU128::checked_from_rational(self.leased, capacity).unwrap_or_default()
+	}
+
+	/// replace the allocated share `old` of a resource with `new`
+	pub fn reallocate(&mut self, old: Perbill, new: Perbill) {
+		self.leased = self
+			.leased
+			.saturating_sub(old.deconstruct().into())
+			.saturating_add(new.deconstruct().into());
+	}
+}
+
+/// unit prices a dynamically priced resource is kept between
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PriceBounds {
+	pub floor: u128,
+	pub ceiling: u128,
+}
+
+impl PriceBounds {
+	pub fn new(floor: u128, ceiling: u128) -> Self {
+		PriceBounds { floor, ceiling }
+	}
+
+	pub fn is_valid(&self) -> bool {
+		self.floor <= self.ceiling
+	}
+
+	/// `unit_price` moved between the floor and the ceiling
+	pub fn clamp(&self, unit_price: u128) -> u128 {
+		unit_price.max(self.floor).min(self.ceiling)
+	}
+}
+
+/// how the bids of an auction are placed
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AuctionKind<BlockNumber> {
+	/// open ascending bids, the highest one when the auction closes wins
+	English,
+	/// bids are committed as hashes until `reveal_start`, then revealed until the auction closes
+	SealedBid { reveal_start: BlockNumber },
+}
+
+/// auction of a lease of a whole resource, the winning bid becomes the lease unit price
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Auction<BlockNumber, AccountId> {
+	/// auction index
+	pub index: u64,
+	/// auctioned resource index
+	pub resource_index: u64,
+	/// provider account
+	pub provider: AccountId,
+	/// english or sealed-bid
+	pub kind: AuctionKind<BlockNumber>,
+	/// lowest unit price accepted
+	pub reserve_price: u128,
+	/// number of blocks leased to the winner from the close of the auction
+	pub lease_duration: BlockNumber,
+	/// block at which the auction closes
+	pub close_at: BlockNumber,
+	/// highest bid so far, as `(bidder, unit price)`
+	pub best_bid: Option<(AccountId, u128)>,
+	/// number of sealed bids committed
+	pub commitments: u32,
+}
+
+impl<BlockNumber, AccountId> Auction<BlockNumber, AccountId> {
+	pub fn new(
+		index: u64,
+		resource_index: u64,
+		provider: AccountId,
+		kind: AuctionKind<BlockNumber>,
+		reserve_price: u128,
+		lease_duration: BlockNumber,
+		close_at: BlockNumber,
+	) -> Self {
+		Auction {
+			index,
+			resource_index,
+			provider,
+			kind,
+			reserve_price,
+			lease_duration,
+			close_at,
+			best_bid: None,
+			commitments: 0,
+		}
+	}
+}
+
+/// resource requirements of a renter
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	Encode,
+	Decode,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+#[scale_info(skip_type_params(StringLimit))]
+#[codec(mel_bound())]
+pub struct ResourceDemand<StringLimit: Get<u32>> {
+	/// minimum number of cpus
+	pub cpu: u64,
+	/// minimum memory
+	pub memory: u64,
+	/// minimum specification
+	pub specification: Specification,
+	/// required operating system
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde::option"))]
+	pub system: Option<BoundedVec<u8, StringLimit>>,
+	/// required cpu model
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde::option"))]
+	pub cpu_model: Option<BoundedVec<u8, StringLimit>>,
+	/// minimum storage, bandwidth and accelerators, each one must be provided by the resource
+	#[cfg_attr(feature = "std", serde(with = "bounded_serde"))]
+	pub dimensions: ResourceDimensions<StringLimit>,
+}
+
+impl<StringLimit: Get<u32>> ResourceDemand<StringLimit> {
+	pub fn new(
+		cpu: u64,
+		memory: u64,
+		specification: Specification,
+		system: Option<BoundedVec<u8, StringLimit>>,
+		cpu_model: Option<BoundedVec<u8, StringLimit>>,
+		dimensions: ResourceDimensions<StringLimit>,
+	) -> Self {
+		ResourceDemand { cpu, memory, specification, system, cpu_model, dimensions }
+	}
+
+	/// whether a resource with `config` and `specification` satisfies the demand
+	pub fn is_satisfied_by(
+		&self,
+		config: &ResourceConfig<StringLimit>,
+		specification: &Specification,
+	) -> bool {
+		config.cpu >= self.cpu &&
+			config.memory >= self.memory &&
+			*specification >= self.specification &&
+			self.system.as_ref().is_none_or(|system| *system == config.system) &&
+			self.cpu_model.as_ref().is_none_or(|cpu_model| *cpu_model == config.cpu_model) &&
+			self.dimensions.iter().all(|dimension| config.provides(dimension))
+	}
+}
+
+/// how a resource is chosen among the ones matching a demand
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MatchPolicy {
+	/// lowest unit price
+	Cheapest,
+	/// best reputation
+	BestReputation,
+}
+
+/// (de)serialize bounded vectors as plain vectors
+#[cfg(feature = "std")]
+mod bounded_serde {
+	use super::*;
+	use serde::{de::Error, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer, Item: Serialize, Bound>(
+		value: &BoundedVec<Item, Bound>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		value.as_slice().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>, Item: Deserialize<'de>, Bound: Get<u32>>(
+		deserializer: D,
+	) -> Result<BoundedVec<Item, Bound>, D::Error> {
+		Vec::<Item>::deserialize(deserializer)?
+			.try_into()
+			.map_err(|_| D::Error::custom("vector exceeds its bound"))
+	}
+
+	pub mod option {
+		use super::*;
+
+		pub fn serialize<S: Serializer, Bound>(
+			value: &Option<BoundedVec<u8, Bound>>,
+			serializer: S,
+		) -> Result<S::Ok, S::Error> {
+			value.as_ref().map(|value| value.as_slice()).serialize(serializer)
+		}
+
+		pub fn deserialize<'de, D: Deserializer<'de>, Bound: Get<u32>>(
+			deserializer: D,
+		) -> Result<Option<BoundedVec<u8, Bound>>, D::Error> {
+			Option::<Vec<u8>>::deserialize(deserializer)?
+				.map(|value| {
+					value.try_into().map_err(|_| D::Error::custom("byte string exceeds its bound"))
+				})
+				.transpose()
+		}
+	}
 }
 
 pub trait ProviderInterface<AccountId> {
 	fn get_providers_points() -> (Vec<(AccountId, ProviderPoints)>, u128, u128);
-	fn create_resource_by_benchmarking(who: AccountId);
 }